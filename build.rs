@@ -0,0 +1,167 @@
+//! Generates a manifest of the entities and textures used by each map in `assets/maps`.
+//! The game uses it to preload all assets a level needs before spawning it.
+
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+const ASSETS_DIR: &str = "assets";
+const MAPS_DIR: &str = "assets/maps";
+const TEXTURES_DIR: &str = "assets/textures";
+
+fn main() {
+    println!("cargo:rerun-if-changed={MAPS_DIR}");
+    println!("cargo:rerun-if-changed={TEXTURES_DIR}");
+
+    let mut maps = Vec::new();
+    collect_maps(Path::new(MAPS_DIR), &mut maps);
+    maps.sort();
+
+    let mut manifest = String::from("pub(crate) const MAP_MANIFESTS: &[MapManifest] = &[\n");
+    for map in maps {
+        println!("cargo:rerun-if-changed={}", map.display());
+        let bytes = fs::read(&map).unwrap_or_else(|err| panic!("Failed to read {map:?}: {err}"));
+        let parsed = parse_map(&String::from_utf8_lossy(&bytes));
+
+        let mut textures: Vec<_> = parsed
+            .textures
+            .iter()
+            .filter_map(|name| texture_path(name))
+            .collect();
+        textures.sort();
+        textures.dedup();
+
+        manifest.push_str("    MapManifest {\n");
+        manifest.push_str(&format!("        path: {:?},\n", asset_path(&map)));
+        manifest.push_str(&format!("        hash: {},\n", fnv1a(&bytes)));
+        manifest.push_str("        entities: &[\n");
+        for entity in parsed.entities {
+            let Some(classname) = entity
+                .iter()
+                .find(|(key, _)| key == "classname")
+                .map(|(_, value)| value)
+            else {
+                continue;
+            };
+            manifest.push_str(&format!(
+                "            MapEntity {{ classname: {classname:?}, properties: &{entity:?} }},\n"
+            ));
+        }
+        manifest.push_str("        ],\n");
+        manifest.push_str(&format!("        textures: &{textures:?},\n"));
+        manifest.push_str("    },\n");
+    }
+    manifest.push_str("];\n");
+
+    let out_dir = PathBuf::from(env::var("OUT_DIR").unwrap());
+    fs::write(out_dir.join("map_manifest.rs"), manifest).unwrap();
+}
+
+fn collect_maps(dir: &Path, maps: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            collect_maps(&path, maps);
+        } else if path
+            .extension()
+            .is_some_and(|extension| extension == "map" || extension == "bsp")
+        {
+            maps.push(path);
+        }
+    }
+}
+
+/// The path of a file relative to the assets directory, as the asset server expects it.
+fn asset_path(path: &Path) -> String {
+    path.strip_prefix(ASSETS_DIR)
+        .unwrap()
+        .to_string_lossy()
+        .replace('\\', "/")
+}
+
+/// A hash that stays the same across Rust versions, unlike the ones in `std`.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+/// Resolves a texture name the way bevy_trenchbroom does:
+/// a `.material.toml` file takes precedence over a plain `.png`.
+/// Returns `None` for textures that don't exist on disk.
+fn texture_path(name: &str) -> Option<String> {
+    ["material.toml", "png"]
+        .into_iter()
+        .map(|extension| Path::new(TEXTURES_DIR).join(format!("{name}.{extension}")))
+        .find(|path| path.is_file())
+        .map(|path| asset_path(&path))
+}
+
+#[derive(Default)]
+struct ParsedMap {
+    /// The key-value pairs of each entity, in the order they appear in the map.
+    entities: Vec<Vec<(String, String)>>,
+    /// The names of all textures on brush faces, including duplicates.
+    textures: Vec<String>,
+}
+
+/// Reads the entities and brush textures of a map.
+/// This works for both `.map` files and the entity lump of `.bsp` files, which both store entities as text.
+/// `.bsp` files embed their textures, so no textures are found for them.
+fn parse_map(text: &str) -> ParsedMap {
+    let mut parsed = ParsedMap::default();
+    let mut depth = 0_usize;
+    let mut entity = Vec::new();
+    for line in text.lines().map(str::trim) {
+        match line {
+            "{" => {
+                if depth == 0 {
+                    entity = Vec::new();
+                }
+                depth += 1;
+            }
+            "}" => {
+                depth = depth.saturating_sub(1);
+                if depth == 0 {
+                    parsed.entities.push(std::mem::take(&mut entity));
+                }
+            }
+            _ if depth == 1 && line.starts_with('"') => {
+                if let Some(pair) = key_value(line) {
+                    entity.push(pair);
+                }
+            }
+            _ if depth == 2 && line.starts_with('(') => {
+                if let Some(texture) = face_texture(line) {
+                    parsed.textures.push(texture);
+                }
+            }
+            _ => {}
+        }
+    }
+    parsed
+}
+
+/// Parses a `"key" "value"` line.
+fn key_value(line: &str) -> Option<(String, String)> {
+    let mut parts = line.split('"');
+    let (Some(""), Some(key), Some(_), Some(value)) =
+        (parts.next(), parts.next(), parts.next(), parts.next())
+    else {
+        return None;
+    };
+    Some((key.to_string(), value.to_string()))
+}
+
+/// Finds the texture name of a brush face, which follows the face's three points,
+/// e.g. `( 0 0 0 ) ( 0 1 0 ) ( 1 0 0 ) wood [ 1 0 0 0 ] [ 0 -1 0 0 ] 0 1 1`.
+fn face_texture(line: &str) -> Option<String> {
+    let (after_points, _) = line.match_indices(')').nth(2)?;
+    let rest = &line[after_points + 1..];
+    let texture = rest.split_whitespace().next()?.trim_matches('"');
+    Some(texture.to_string())
+}
@@ -0,0 +1,333 @@
+//! A high-level way to load collections of asset handles as resources.
+
+mod placeholder;
+
+use std::{collections::VecDeque, time::Duration};
+
+use bevy::{
+    asset::{AssetPath, RecursiveDependencyLoadState, UntypedAssetId},
+    prelude::*,
+};
+
+pub(crate) use placeholder::insert_failed_resources_with_placeholders;
+use placeholder::insert_with_placeholders;
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<ResourceHandles>();
+    app.add_systems(PreUpdate, load_resource_assets);
+}
+
+pub(crate) trait LoadResource {
+    /// This will load the [`Resource`] as an [`Asset`]. When all of its asset dependencies
+    /// have been loaded, it will be inserted as a resource. This ensures that the resource only
+    /// exists when the assets are ready.
+    fn load_resource<T: Resource + Asset + Clone + FromWorld>(&mut self) -> &mut Self {
+        self.load_resource_with_priority::<T>(LoadPriority::Blocking)
+    }
+
+    /// Like [`LoadResource::load_resource`], but the loading screen does not wait for the resource.
+    /// Use [`resource_exists`] to run systems only once it is available.
+    fn load_resource_in_background<T: Resource + Asset + Clone + FromWorld>(
+        &mut self,
+    ) -> &mut Self {
+        self.load_resource_with_priority::<T>(LoadPriority::Background)
+    }
+
+    fn load_resource_with_priority<T: Resource + Asset + Clone + FromWorld>(
+        &mut self,
+        priority: LoadPriority,
+    ) -> &mut Self;
+}
+
+/// Whether the loading screen waits for a resource.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub(crate) enum LoadPriority {
+    /// The loading screen waits until the resource is inserted.
+    #[default]
+    Blocking,
+    /// The resource streams in whenever it is ready, e.g. during gameplay.
+    /// If its assets fail to load, placeholders are used without asking the player.
+    Background,
+}
+
+impl LoadResource for App {
+    fn load_resource_with_priority<T: Resource + Asset + Clone + FromWorld>(
+        &mut self,
+        priority: LoadPriority,
+    ) -> &mut Self {
+        self.init_asset::<T>();
+        self.world_mut().load_resource_with_priority::<T>(priority);
+        self
+    }
+}
+
+/// Allows loading resources at runtime, e.g. when their contents depend on other resources.
+/// The asset type still needs to be registered with [`App::init_asset`] beforehand.
+impl LoadResource for World {
+    fn load_resource_with_priority<T: Resource + Asset + Clone + FromWorld>(
+        &mut self,
+        priority: LoadPriority,
+    ) -> &mut Self {
+        let value = T::from_world(self);
+        let assets = self.resource::<AssetServer>();
+        let handle = assets.add(value);
+        let mut handles = self.resource_mut::<ResourceHandles>();
+        handles.waiting.push_back(WaitingResource {
+            handle: handle.untyped(),
+            type_name: T::short_type_path(),
+            priority,
+            insert: |world, handle| {
+                let assets = world.resource::<Assets<T>>();
+                if let Some(value) = assets.get(handle.id().typed::<T>()) {
+                    world.insert_resource(value.clone());
+                }
+            },
+            dependencies: |world, handle| {
+                let mut dependencies = Vec::new();
+                if let Some(value) = world.resource::<Assets<T>>().get(handle.id().typed::<T>()) {
+                    value.visit_dependencies(&mut |id| dependencies.push(id));
+                }
+                dependencies
+            },
+        });
+        self
+    }
+}
+
+/// A function that inserts a loaded resource.
+type InsertLoadedResource = fn(&mut World, &UntypedHandle);
+/// A function that lists the assets a resource depends on.
+type ListDependencies = fn(&World, &UntypedHandle) -> Vec<UntypedAssetId>;
+
+struct WaitingResource {
+    handle: UntypedHandle,
+    type_name: &'static str,
+    priority: LoadPriority,
+    insert: InsertLoadedResource,
+    dependencies: ListDependencies,
+}
+
+/// How long to wait for resources before reporting them as stuck.
+const LOAD_TIMEOUT: Duration = Duration::from_secs(30);
+
+#[derive(Resource, Default)]
+pub(crate) struct ResourceHandles {
+    // Use a queue for waiting assets so they can be cycled through and moved to
+    // `finished` one at a time.
+    waiting: VecDeque<WaitingResource>,
+    finished: Vec<UntypedHandle>,
+    /// Only contains [`LoadPriority::Blocking`] resources, background resources use placeholders right away.
+    failed: Vec<FailedResource>,
+    /// The number of blocking resources that were finished since the current batch started loading.
+    finished_in_batch: usize,
+    /// When the current batch of blocking resources started loading, in [`Time<Real>`].
+    batch_start: Option<Duration>,
+    progress: LoadingProgress,
+}
+
+/// A snapshot of the resources that are currently loading.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct LoadingProgress {
+    /// Resources of the current batch that have been inserted.
+    pub(crate) finished: usize,
+    /// All resources of the current batch.
+    pub(crate) total: usize,
+    /// The size of the loaded audio files, the only assets that expose their size in bytes.
+    pub(crate) loaded_bytes: usize,
+    /// The type name of the resource that is loaded next.
+    pub(crate) current: Option<&'static str>,
+    pub(crate) pending: Vec<PendingResource>,
+    /// Whether the current batch has been loading for longer than expected.
+    pub(crate) timed_out: bool,
+}
+
+impl LoadingProgress {
+    /// The fraction of loaded assets in `[0, 1]`, counting the dependencies of pending resources.
+    pub(crate) fn fraction(&self) -> f32 {
+        if self.total == 0 {
+            return 1.0;
+        }
+        let pending: f32 = self.pending.iter().map(PendingResource::fraction).sum();
+        (self.finished as f32 + pending) / self.total as f32
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct PendingResource {
+    pub(crate) type_name: &'static str,
+    pub(crate) loaded_dependencies: usize,
+    pub(crate) total_dependencies: usize,
+    /// Paths of the dependencies that failed to load, including those whose own dependencies failed.
+    pub(crate) failed: Vec<AssetPath<'static>>,
+}
+
+impl PendingResource {
+    fn fraction(&self) -> f32 {
+        if self.total_dependencies == 0 {
+            return 0.0;
+        }
+        self.loaded_dependencies as f32 / self.total_dependencies as f32
+    }
+}
+
+/// A resource that will never be inserted on its own because some of its dependencies failed to load.
+pub(crate) struct FailedResource {
+    waiting: WaitingResource,
+    /// Paths of the dependencies that failed to load, including those whose own dependencies failed.
+    pub(crate) failed_paths: Vec<AssetPath<'static>>,
+}
+
+impl FailedResource {
+    pub(crate) fn type_name(&self) -> &'static str {
+        self.waiting.type_name
+    }
+}
+
+impl ResourceHandles {
+    /// Returns true if all [`Asset`]s requested with [`LoadPriority::Blocking`] have finished loading
+    /// and are available as [`Resource`]s.
+    pub(crate) fn is_all_done(&self) -> bool {
+        self.blocking().next().is_none() && self.failed.is_empty()
+    }
+
+    fn blocking(&self) -> impl Iterator<Item = &WaitingResource> {
+        self.waiting
+            .iter()
+            .filter(|waiting| waiting.priority == LoadPriority::Blocking)
+    }
+
+    pub(crate) fn progress(&self) -> &LoadingProgress {
+        &self.progress
+    }
+
+    pub(crate) fn failed(&self) -> &[FailedResource] {
+        &self.failed
+    }
+
+    /// Gives the current batch another [`LOAD_TIMEOUT`] before it is reported as stuck again.
+    pub(crate) fn restart_timeout(&mut self) {
+        self.batch_start = None;
+        self.progress.timed_out = false;
+    }
+}
+
+/// Run condition that is true when a resource cannot be inserted because its assets failed to load.
+pub(crate) fn any_resource_failed(resource_handles: Res<ResourceHandles>) -> bool {
+    !resource_handles.failed.is_empty()
+}
+
+/// Run condition that is true when the blocking resources have been loading for longer than [`LOAD_TIMEOUT`].
+pub(crate) fn loading_timed_out(resource_handles: Res<ResourceHandles>) -> bool {
+    resource_handles.progress.timed_out
+}
+
+fn load_resource_assets(world: &mut World) {
+    let mut failed_in_background = Vec::new();
+    world.resource_scope(|world, mut resource_handles: Mut<ResourceHandles>| {
+        world.resource_scope(|world, assets: Mut<AssetServer>| {
+            for _ in 0..resource_handles.waiting.len() {
+                let waiting = resource_handles.waiting.pop_front().unwrap();
+                if assets.is_loaded_with_dependencies(&waiting.handle) {
+                    (waiting.insert)(world, &waiting.handle);
+                    if waiting.priority == LoadPriority::Blocking {
+                        resource_handles.finished_in_batch += 1;
+                    }
+                    resource_handles.finished.push(waiting.handle);
+                } else if matches!(
+                    assets.get_recursive_dependency_load_state(&waiting.handle),
+                    Some(RecursiveDependencyLoadState::Failed(_))
+                ) {
+                    let dependencies = (waiting.dependencies)(world, &waiting.handle);
+                    let failed_paths = failed_dependency_paths(&assets, &dependencies);
+                    for path in &failed_paths {
+                        error!(
+                            "Failed to load \"{path}\", which is needed by {}",
+                            waiting.type_name
+                        );
+                    }
+                    let failed = FailedResource {
+                        waiting,
+                        failed_paths,
+                    };
+                    match failed.waiting.priority {
+                        LoadPriority::Blocking => resource_handles.failed.push(failed),
+                        LoadPriority::Background => failed_in_background.push(failed),
+                    }
+                } else {
+                    resource_handles.waiting.push_back(waiting);
+                }
+            }
+        });
+        let now = world.resource::<Time<Real>>().elapsed();
+        update_progress(world, &mut resource_handles, now);
+    });
+    for failed in failed_in_background {
+        insert_with_placeholders(world, failed);
+    }
+}
+
+fn update_progress(world: &World, resource_handles: &mut ResourceHandles, now: Duration) {
+    if resource_handles.blocking().next().is_none() {
+        resource_handles.batch_start = None;
+        resource_handles.finished_in_batch = 0;
+        resource_handles.progress = default();
+        return;
+    }
+    let batch_start = *resource_handles.batch_start.get_or_insert(now);
+    let blocking: Vec<_> = resource_handles.blocking().collect();
+
+    let assets = world.resource::<AssetServer>();
+    let audio = world.resource::<Assets<AudioSource>>();
+    let mut loaded_bytes = 0;
+    let pending = blocking
+        .iter()
+        .map(|waiting| {
+            let dependencies = (waiting.dependencies)(world, &waiting.handle);
+            let mut loaded_dependencies = 0;
+            for &id in &dependencies {
+                if assets.is_loaded_with_dependencies(id) {
+                    loaded_dependencies += 1;
+                    if let Some(source) = id.try_typed().ok().and_then(|id| audio.get(id)) {
+                        loaded_bytes += source.bytes.len();
+                    }
+                }
+            }
+            let failed = failed_dependency_paths(assets, &dependencies);
+            PendingResource {
+                type_name: waiting.type_name,
+                loaded_dependencies,
+                total_dependencies: dependencies.len(),
+                failed,
+            }
+        })
+        .collect();
+
+    let finished = resource_handles.finished_in_batch;
+    let current = blocking.first().map(|waiting| waiting.type_name);
+    let total = finished + blocking.len() + resource_handles.failed.len();
+    resource_handles.progress = LoadingProgress {
+        finished,
+        total,
+        loaded_bytes,
+        current,
+        pending,
+        timed_out: now.saturating_sub(batch_start) > LOAD_TIMEOUT,
+    };
+}
+
+fn failed_dependency_paths(
+    assets: &AssetServer,
+    dependencies: &[UntypedAssetId],
+) -> Vec<AssetPath<'static>> {
+    dependencies
+        .iter()
+        .filter(|&&id| {
+            matches!(
+                assets.get_recursive_dependency_load_state(id),
+                Some(RecursiveDependencyLoadState::Failed(_))
+            )
+        })
+        .filter_map(|&id| assets.get_path(id))
+        .map(AssetPath::into_owned)
+        .collect()
+}
@@ -0,0 +1,124 @@
+//! Placeholder assets that stand in for assets that failed to load,
+//! so that the game can continue instead of getting stuck on the loading screen.
+
+use bevy::{
+    asset::{LoadState, RecursiveDependencyLoadState, RenderAssetUsages, UntypedAssetId},
+    prelude::*,
+    render::render_resource::{Extent3d, TextureDimension, TextureFormat},
+};
+
+use super::{FailedResource, ResourceHandles};
+
+/// The usual "something is missing here" color.
+const PLACEHOLDER_COLOR: [u8; 4] = [255, 0, 255, 255];
+
+/// Substitutes placeholders for all assets that failed to load and inserts the resources depending on them.
+pub(crate) fn insert_failed_resources_with_placeholders(world: &mut World) {
+    let failed = std::mem::take(&mut world.resource_mut::<ResourceHandles>().failed);
+    for resource in failed {
+        insert_with_placeholders(world, resource);
+    }
+}
+
+pub(super) fn insert_with_placeholders(world: &mut World, resource: FailedResource) {
+    let waiting = resource.waiting;
+    for id in (waiting.dependencies)(world, &waiting.handle) {
+        let assets = world.resource::<AssetServer>();
+        let has_failed = matches!(assets.get_load_state(id), Some(LoadState::Failed(_)));
+        // E.g. a model that loaded fine, but whose textures did not.
+        // We can't reach those nested assets from here, so the whole dependency is replaced instead.
+        let has_failed_dependency = matches!(
+            assets.get_recursive_dependency_load_state(id),
+            Some(RecursiveDependencyLoadState::Failed(_))
+        );
+        if has_failed || has_failed_dependency {
+            insert_placeholder(world, id);
+        }
+    }
+    warn!("Using placeholders for {}", waiting.type_name);
+    (waiting.insert)(world, &waiting.handle);
+    world
+        .resource_mut::<ResourceHandles>()
+        .finished
+        .push(waiting.handle);
+}
+
+fn insert_placeholder(world: &mut World, id: UntypedAssetId) {
+    if let Ok(id) = id.try_typed::<Mesh>() {
+        world
+            .resource_mut::<Assets<Mesh>>()
+            .insert(id, placeholder_mesh());
+    } else if let Ok(id) = id.try_typed::<Image>() {
+        world
+            .resource_mut::<Assets<Image>>()
+            .insert(id, placeholder_image());
+    } else if let Ok(id) = id.try_typed::<AudioSource>() {
+        world
+            .resource_mut::<Assets<AudioSource>>()
+            .insert(id, silent_audio());
+    } else if let Ok(id) = id.try_typed::<Scene>() {
+        let scene = placeholder_scene(world);
+        world.resource_mut::<Assets<Scene>>().insert(id, scene);
+    } else {
+        warn!("No placeholder available for asset {id:?}");
+    }
+}
+
+fn placeholder_mesh() -> Mesh {
+    Cuboid::from_length(0.5).into()
+}
+
+fn placeholder_image() -> Image {
+    Image::new_fill(
+        Extent3d::default(),
+        TextureDimension::D2,
+        &PLACEHOLDER_COLOR,
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::default(),
+    )
+}
+
+/// A scene containing only a placeholder mesh, used in place of models.
+fn placeholder_scene(world: &mut World) -> Scene {
+    let mesh = world.resource_mut::<Assets<Mesh>>().add(placeholder_mesh());
+    let [r, g, b, a] = PLACEHOLDER_COLOR;
+    let material = world
+        .resource_mut::<Assets<StandardMaterial>>()
+        .add(Color::srgba_u8(r, g, b, a));
+    let mut scene_world = World::new();
+    scene_world.spawn((
+        Name::new("Placeholder"),
+        Mesh3d(mesh),
+        MeshMaterial3d(material),
+    ));
+    Scene::new(scene_world)
+}
+
+/// A tenth of a second of silence, encoded as a WAV file.
+fn silent_audio() -> AudioSource {
+    const SAMPLE_RATE: u32 = 44_100;
+    const SAMPLES: u32 = SAMPLE_RATE / 10;
+    const BYTES_PER_SAMPLE: u32 = 2;
+    let data_size = SAMPLES * BYTES_PER_SAMPLE;
+
+    let mut bytes = Vec::with_capacity(44 + data_size as usize);
+    bytes.extend_from_slice(b"RIFF");
+    bytes.extend_from_slice(&(36 + data_size).to_le_bytes());
+    bytes.extend_from_slice(b"WAVE");
+    bytes.extend_from_slice(b"fmt ");
+    bytes.extend_from_slice(&16_u32.to_le_bytes());
+    // PCM, mono
+    bytes.extend_from_slice(&1_u16.to_le_bytes());
+    bytes.extend_from_slice(&1_u16.to_le_bytes());
+    bytes.extend_from_slice(&SAMPLE_RATE.to_le_bytes());
+    bytes.extend_from_slice(&(SAMPLE_RATE * BYTES_PER_SAMPLE).to_le_bytes());
+    bytes.extend_from_slice(&(BYTES_PER_SAMPLE as u16).to_le_bytes());
+    bytes.extend_from_slice(&(BYTES_PER_SAMPLE as u16 * 8).to_le_bytes());
+    bytes.extend_from_slice(b"data");
+    bytes.extend_from_slice(&data_size.to_le_bytes());
+    bytes.resize(44 + data_size as usize, 0);
+
+    AudioSource {
+        bytes: bytes.into(),
+    }
+}
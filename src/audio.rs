@@ -1,15 +1,188 @@
-use bevy::prelude::*;
+//! Audio is grouped into buses, one per category marker component.
+//! Each bus has a volume resource that is applied to every sink tagged with its marker,
+//! including sounds that are already playing.
+
+use bevy::{audio::Volume, math::StableInterpolate as _, prelude::*};
+use bevy_yarnspinner::prelude::DialogueRunner;
+
+use crate::{settings::Settings, third_party::bevy_yarnspinner::is_dialogue_running};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<(MusicVolume, SoundEffectVolume)>();
+    app.init_resource::<MusicVolume>();
+    app.init_resource::<SoundEffectVolume>();
+    app.add_systems(
+        Update,
+        (
+            sync_volumes_with_settings.run_if(resource_changed::<Settings>),
+            duck_music_during_dialogue,
+            apply_volumes.run_if(
+                resource_changed::<GlobalVolume>
+                    .or(resource_changed::<MusicVolume>)
+                    .or(resource_changed::<SoundEffectVolume>),
+            ),
+            apply_volumes_to_new_sinks,
+        )
+            .chain(),
+    );
+}
 
 /// An organizational marker component that should be added to a spawned [`AudioPlayer`] if it is in the
 /// general "music" category (ex: global background music, soundtrack, etc).
 ///
-/// This can then be used to query for and operate on sounds in that category.
+/// Its volume is controlled by [`MusicVolume`].
 #[derive(Component, Default)]
 pub(crate) struct Music;
 
 /// An organizational marker component that should be added to a spawned [`AudioPlayer`] if it is in the
 /// general "sound effect" category (ex: footsteps, the sound of a magic spell, a door opening).
 ///
-/// This can then be used to query for and operate on sounds in that category.
+/// Its volume is controlled by [`SoundEffectVolume`].
 #[derive(Component, Default)]
 pub(crate) struct SoundEffect;
+
+/// The volume of all sounds tagged with [`Music`].
+#[derive(Resource, Reflect, Debug, Clone, Copy, PartialEq)]
+#[reflect(Resource)]
+pub(crate) struct MusicVolume {
+    /// The volume chosen by the user.
+    pub(crate) volume: f32,
+    /// A temporary multiplier used to make other sounds easier to hear, e.g. during dialogue.
+    pub(crate) ducking: f32,
+}
+
+impl Default for MusicVolume {
+    fn default() -> Self {
+        Self {
+            volume: 1.0,
+            ducking: 1.0,
+        }
+    }
+}
+
+/// The volume of all sounds tagged with [`SoundEffect`].
+#[derive(Resource, Reflect, Debug, Clone, Copy, PartialEq)]
+#[reflect(Resource)]
+pub(crate) struct SoundEffectVolume(pub(crate) f32);
+
+impl Default for SoundEffectVolume {
+    fn default() -> Self {
+        Self(1.0)
+    }
+}
+
+/// The music ducking multiplier while a dialogue is running.
+const DUCKED_MUSIC: f32 = 0.35;
+/// How quickly the music fades in and out of ducking, see `StableInterpolate::smooth_nudge`.
+const DUCKING_DECAY_RATE: f32 = 4.0;
+
+fn sync_volumes_with_settings(
+    settings: Res<Settings>,
+    mut global_volume: ResMut<GlobalVolume>,
+    mut music_volume: ResMut<MusicVolume>,
+    mut sound_effect_volume: ResMut<SoundEffectVolume>,
+) {
+    // Bevy only applies the global volume to new sinks, `apply_volumes` takes care of the rest.
+    global_volume.volume = Volume::new(settings.master_volume);
+    music_volume.volume = settings.music_volume;
+    sound_effect_volume.0 = settings.sound_effect_volume;
+}
+
+fn duck_music_during_dialogue(
+    dialogue_runner: Option<Single<&DialogueRunner>>,
+    mut music_volume: ResMut<MusicVolume>,
+    time: Res<Time>,
+) {
+    let target = if is_dialogue_running(dialogue_runner) {
+        DUCKED_MUSIC
+    } else {
+        1.0
+    };
+    if music_volume.ducking == target {
+        return;
+    }
+    let mut ducking = music_volume.ducking;
+    ducking.smooth_nudge(&target, DUCKING_DECAY_RATE, time.delta_secs());
+    // Snap to the target so that we stop touching the sinks every frame.
+    if (ducking - target).abs() < 0.01 {
+        ducking = target;
+    }
+    music_volume.ducking = ducking;
+}
+
+/// Parameters needed to compute the volume of any sink.
+#[derive(Clone, Copy)]
+struct Buses<'a> {
+    global: &'a GlobalVolume,
+    music: &'a MusicVolume,
+    sound_effect: &'a SoundEffectVolume,
+}
+
+impl Buses<'_> {
+    /// Computes the final volume of a sink the same way Bevy does when it is created,
+    /// but with the category's bus applied on top.
+    fn volume_of(&self, playback: &PlaybackSettings, is_music: bool, is_sound_effect: bool) -> f32 {
+        let bus_volume = if is_music {
+            self.music.volume * self.music.ducking
+        } else if is_sound_effect {
+            self.sound_effect.0
+        } else {
+            1.0
+        };
+        playback.volume.get() * self.global.volume.get() * bus_volume
+    }
+}
+
+fn apply_volumes(
+    global_volume: Res<GlobalVolume>,
+    music_volume: Res<MusicVolume>,
+    sound_effect_volume: Res<SoundEffectVolume>,
+    q_sink: Query<(&PlaybackSettings, &AudioSink, Has<Music>, Has<SoundEffect>)>,
+    q_spatial_sink: Query<(
+        &PlaybackSettings,
+        &SpatialAudioSink,
+        Has<Music>,
+        Has<SoundEffect>,
+    )>,
+) {
+    let buses = Buses {
+        global: &global_volume,
+        music: &music_volume,
+        sound_effect: &sound_effect_volume,
+    };
+    for (playback, sink, is_music, is_sound_effect) in &q_sink {
+        sink.set_volume(buses.volume_of(playback, is_music, is_sound_effect));
+    }
+    for (playback, sink, is_music, is_sound_effect) in &q_spatial_sink {
+        sink.set_volume(buses.volume_of(playback, is_music, is_sound_effect));
+    }
+}
+
+/// Bevy only applies the [`GlobalVolume`] to new sinks, so we need to apply the bus volume ourselves.
+fn apply_volumes_to_new_sinks(
+    global_volume: Res<GlobalVolume>,
+    music_volume: Res<MusicVolume>,
+    sound_effect_volume: Res<SoundEffectVolume>,
+    q_sink: Query<(&PlaybackSettings, &AudioSink, Has<Music>, Has<SoundEffect>), Added<AudioSink>>,
+    q_spatial_sink: Query<
+        (
+            &PlaybackSettings,
+            &SpatialAudioSink,
+            Has<Music>,
+            Has<SoundEffect>,
+        ),
+        Added<SpatialAudioSink>,
+    >,
+) {
+    let buses = Buses {
+        global: &global_volume,
+        music: &music_volume,
+        sound_effect: &sound_effect_volume,
+    };
+    for (playback, sink, is_music, is_sound_effect) in &q_sink {
+        sink.set_volume(buses.volume_of(playback, is_music, is_sound_effect));
+    }
+    for (playback, sink, is_music, is_sound_effect) in &q_spatial_sink {
+        sink.set_volume(buses.volume_of(playback, is_music, is_sound_effect));
+    }
+}
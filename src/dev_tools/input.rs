@@ -15,6 +15,10 @@ pub(crate) struct ToggleDebugUi;
 #[input_action(output = bool)]
 pub(crate) struct ForceFreeCursor;
 
+#[derive(Debug, InputAction)]
+#[input_action(output = bool)]
+pub(crate) struct LoadStressTestLevel;
+
 #[derive(Debug, InputContext, Default)]
 struct DevToolsInputContext;
 
@@ -24,6 +28,7 @@ fn dev_tools_input_binding(
 ) {
     actions.bind::<ToggleDebugUi>().to(KeyCode::F3);
     actions.bind::<ForceFreeCursor>().to(KeyCode::Backquote);
+    actions.bind::<LoadStressTestLevel>().to(KeyCode::F9);
 }
 
 fn setup_dev_tools_input(mut commands: Commands) {
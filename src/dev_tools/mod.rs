@@ -4,6 +4,7 @@ use bevy::{dev_tools::states::log_transitions, prelude::*};
 
 mod debug_ui;
 mod input;
+mod stress_test;
 mod validate_preloading;
 
 use crate::screens::Screen;
@@ -12,5 +13,10 @@ pub(super) fn plugin(app: &mut App) {
     // Log `Screen` state transitions.
     app.add_systems(Update, log_transitions::<Screen>);
 
-    app.add_plugins((input::plugin, debug_ui::plugin, validate_preloading::plugin));
+    app.add_plugins((
+        input::plugin,
+        debug_ui::plugin,
+        stress_test::plugin,
+        validate_preloading::plugin,
+    ));
 }
@@ -0,0 +1,44 @@
+//! A level with dozens of NPCs to check that the NPC systems scale.
+//! Press F9 on the title screen or during gameplay to load it.
+
+use bevy::prelude::*;
+use bevy_enhanced_input::prelude::*;
+
+use crate::{
+    gameplay::{
+        level::{CurrentLevel, LevelId, LevelInfo, LevelRegistry},
+        save::PendingSave,
+    },
+    screens::Screen,
+};
+
+use super::input::LoadStressTestLevel;
+
+pub(super) fn plugin(app: &mut App) {
+    app.world_mut()
+        .resource_mut::<LevelRegistry>()
+        .register(LevelInfo {
+            id: stress_test_level(),
+            display_name: "NPC Stress Test".to_string(),
+            map_path: "maps/stress_npcs/stress_npcs.map".to_string(),
+        });
+    app.add_observer(load_stress_test_level);
+}
+
+fn stress_test_level() -> LevelId {
+    LevelId("stress_npcs".to_string())
+}
+
+fn load_stress_test_level(
+    _trigger: Trigger<Started<LoadStressTestLevel>>,
+    screen: Res<State<Screen>>,
+    mut next_screen: ResMut<NextState<Screen>>,
+    mut commands: Commands,
+) {
+    if !matches!(screen.get(), Screen::Title | Screen::Gameplay) {
+        return;
+    }
+    commands.remove_resource::<PendingSave>();
+    commands.insert_resource(CurrentLevel(stress_test_level()));
+    next_screen.set(Screen::Loading);
+}
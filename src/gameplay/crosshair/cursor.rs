@@ -3,7 +3,10 @@ use std::any::Any;
 use bevy::{prelude::*, window::CursorGrabMode};
 use bevy_yarnspinner::events::DialogueStartEvent;
 
-use crate::{AppSet, screens::Screen, third_party::bevy_yarnspinner::is_dialogue_running};
+use crate::{
+    AppSet, menus::is_menu_open, screens::Screen,
+    third_party::bevy_yarnspinner::is_dialogue_running,
+};
 
 use super::CrosshairState;
 
@@ -15,6 +18,7 @@ pub(super) fn plugin(app: &mut App) {
             capture_cursor
                 .param_warn_once()
                 .run_if(not(is_dialogue_running))
+                .run_if(not(is_menu_open))
                 .run_if(not(is_cursor_forced_freed)),
             release_cursor
                 .param_warn_once()
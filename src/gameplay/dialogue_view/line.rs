@@ -0,0 +1,162 @@
+//! Types out lines letter by letter and advances the dialogue once the player has read them.
+
+use bevy::prelude::*;
+use bevy_enhanced_input::prelude::*;
+use bevy_yarnspinner::{events::PresentLineEvent, prelude::*};
+
+use crate::{
+    gameplay::player::dialogue_input::{AdvanceDialogue, SkipLine},
+    theme::interaction::OnPress,
+};
+
+use super::{
+    DialogueViewSet, LineMetadata, LineText, SpeakerName, SpeakerNamePlate,
+    options::OptionSelection, portrait::ShowPortrait,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<Typewriter>();
+    app.add_systems(
+        Update,
+        (present_line, type_line).chain().in_set(DialogueViewSet),
+    );
+    app.add_observer(advance_dialogue);
+    app.add_observer(skip_line);
+}
+
+/// How many characters are typed per second at normal speed.
+const CHARACTERS_PER_SECOND: f32 = 40.0;
+/// Lines ignore attempts to advance them for this long after being presented.
+/// Otherwise, the button press that starts a dialogue would immediately finish typing its first line.
+const MIN_SECONDS_BEFORE_ADVANCING: f32 = 0.2;
+
+/// The line that is currently presented.
+#[derive(Resource, Debug, Default)]
+pub(super) struct Typewriter {
+    /// The line without the speaker's name, or `None` if no line is waiting to be read.
+    line: Option<Vec<char>>,
+    /// How many characters of the line are visible.
+    typed: f32,
+    speed: f32,
+    /// Seconds since the line was presented.
+    elapsed: f32,
+    /// Whether the line should stay visible while the following options are presented.
+    pub(super) is_last_line: bool,
+}
+
+impl Typewriter {
+    fn is_finished(&self) -> bool {
+        self.line
+            .as_ref()
+            .is_none_or(|line| self.typed >= line.len() as f32)
+    }
+
+    fn finish(&mut self) {
+        if let Some(line) = &self.line {
+            self.typed = line.len() as f32;
+        }
+    }
+
+    /// Finishes typing the current line, or continues to the next one if it is already fully shown.
+    fn advance(&mut self, dialogue_runner: &mut DialogueRunner) {
+        if self.line.is_none() || self.elapsed < MIN_SECONDS_BEFORE_ADVANCING {
+            return;
+        }
+        if !self.is_finished() {
+            self.finish();
+            return;
+        }
+        self.line = None;
+        dialogue_runner.continue_in_next_update();
+    }
+}
+
+fn present_line(
+    mut commands: Commands,
+    mut present_line_events: EventReader<PresentLineEvent>,
+    mut typewriter: ResMut<Typewriter>,
+    mut line_text: Single<&mut Text, (With<LineText>, Without<SpeakerName>)>,
+    mut speaker_name: Single<&mut Text, (With<SpeakerName>, Without<LineText>)>,
+    mut name_plate: Single<&mut Node, With<SpeakerNamePlate>>,
+) {
+    for event in present_line_events.read() {
+        let line = &event.line;
+        let metadata = LineMetadata::parse(&line.metadata);
+        let speaker = line.character_name().map(ToString::to_string);
+
+        *typewriter = Typewriter {
+            line: Some(line.text_without_character_name().chars().collect()),
+            typed: 0.0,
+            speed: metadata.speed.unwrap_or(1.0),
+            elapsed: 0.0,
+            is_last_line: metadata.last_line,
+        };
+        line_text.0.clear();
+        speaker_name.0 = speaker.clone().unwrap_or_default();
+        name_plate.display = if speaker.is_some() {
+            Display::Flex
+        } else {
+            Display::None
+        };
+        commands.trigger(ShowPortrait {
+            speaker,
+            portrait: metadata.portrait,
+        });
+    }
+}
+
+fn type_line(
+    time: Res<Time>,
+    mut typewriter: ResMut<Typewriter>,
+    mut line_text: Single<&mut Text, With<LineText>>,
+) {
+    let Typewriter {
+        line: Some(line),
+        typed,
+        speed,
+        elapsed,
+        ..
+    } = typewriter.as_mut()
+    else {
+        return;
+    };
+    *elapsed += time.delta_secs();
+    let previously_typed = line_text.0.chars().count();
+    if previously_typed >= line.len() {
+        return;
+    }
+    *typed += CHARACTERS_PER_SECOND * *speed * time.delta_secs();
+    let visible = (*typed as usize).min(line.len());
+    if visible != previously_typed {
+        line_text.0 = line[..visible].iter().collect();
+    }
+}
+
+fn advance_dialogue(
+    _trigger: Trigger<Started<AdvanceDialogue>>,
+    mut typewriter: ResMut<Typewriter>,
+    options: Option<Res<OptionSelection>>,
+    mut dialogue_runner: Single<&mut DialogueRunner>,
+) {
+    // While options are shown, advancing confirms the selected one instead.
+    if options.is_none() {
+        typewriter.advance(&mut dialogue_runner);
+    }
+}
+
+/// Clicking the dialogue box advances the dialogue just like [`AdvanceDialogue`].
+pub(super) fn advance_dialogue_on_click(
+    _trigger: Trigger<OnPress>,
+    mut typewriter: ResMut<Typewriter>,
+    options: Option<Res<OptionSelection>>,
+    mut dialogue_runner: Single<&mut DialogueRunner>,
+) {
+    if options.is_none() {
+        typewriter.advance(&mut dialogue_runner);
+    }
+}
+
+/// Shows the rest of the current line at once.
+fn skip_line(_trigger: Trigger<Started<SkipLine>>, mut typewriter: ResMut<Typewriter>) {
+    typewriter.finish();
+}
@@ -0,0 +1,204 @@
+//! Shows the dialogue run by `bevy_yarnspinner` using our `theme`.
+//!
+//! Lines are typed out letter by letter below a plate with the speaker's name and an optional portrait.
+//! Options are numbered and can be picked with the number keys, the mouse, or a gamepad.
+//!
+//! The following line metadata is supported, written as hashtags after a line in Yarn:
+//! - `#lastline`: keep showing this line while the options that follow it are presented.
+//! - `#portrait:<path>`: show the image at `<path>` instead of the speaker's usual portrait.
+//! - `#speed:<factor>`: type this line `<factor>` times as fast as usual.
+
+use bevy::prelude::*;
+use bevy_yarnspinner::{
+    events::{DialogueCompleteEvent, DialogueStartEvent},
+    prelude::*,
+};
+
+use crate::{menus::is_menu_open, screens::Screen, theme::palette::*};
+
+mod line;
+mod options;
+mod portrait;
+
+use line::Typewriter;
+use options::OptionSelection;
+
+pub(super) fn plugin(app: &mut App) {
+    app.configure_sets(
+        Update,
+        DialogueViewSet
+            .after(YarnSpinnerSystemSet)
+            .run_if(in_state(Screen::Gameplay).and(not(is_menu_open))),
+    );
+    app.add_plugins((line::plugin, options::plugin, portrait::plugin));
+    app.add_systems(OnEnter(Screen::Gameplay), spawn_dialogue_view);
+    app.add_systems(
+        Update,
+        (
+            show_dialogue_view.run_if(on_event::<DialogueStartEvent>),
+            hide_dialogue_view.run_if(on_event::<DialogueCompleteEvent>),
+        )
+            .in_set(DialogueViewSet),
+    );
+}
+
+/// Systems that present the dialogue. They don't run while a menu is open.
+#[derive(Debug, SystemSet, Hash, Eq, PartialEq, Clone, Copy)]
+pub(crate) struct DialogueViewSet;
+
+#[derive(Component)]
+struct DialogueViewRoot;
+
+#[derive(Component)]
+struct SpeakerNamePlate;
+
+#[derive(Component)]
+struct SpeakerName;
+
+#[derive(Component)]
+struct LineText;
+
+#[derive(Component)]
+struct Portrait;
+
+#[derive(Component)]
+struct OptionList;
+
+fn spawn_dialogue_view(mut commands: Commands) {
+    commands
+        .spawn((
+            Name::new("Dialogue View"),
+            DialogueViewRoot,
+            Node {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::End,
+                padding: UiRect::bottom(Val::Px(40.0)),
+                ..default()
+            },
+            Visibility::Hidden,
+            StateScoped(Screen::Gameplay),
+        ))
+        .with_children(|parent| {
+            parent
+                .spawn((
+                    Name::new("Dialogue Box"),
+                    Node {
+                        width: Val::Px(800.0),
+                        min_height: Val::Px(180.0),
+                        column_gap: Val::Px(20.0),
+                        padding: UiRect::all(Val::Px(20.0)),
+                        ..default()
+                    },
+                    BackgroundColor(OVERLAY_BACKGROUND),
+                    Button,
+                ))
+                .observe(line::advance_dialogue_on_click)
+                .with_children(|parent| {
+                    parent.spawn((
+                        Name::new("Portrait"),
+                        Portrait,
+                        ImageNode::default(),
+                        Node {
+                            width: Val::Px(128.0),
+                            height: Val::Px(128.0),
+                            flex_shrink: 0.0,
+                            display: Display::None,
+                            ..default()
+                        },
+                    ));
+                    parent
+                        .spawn((
+                            Name::new("Dialogue Content"),
+                            Node {
+                                flex_direction: FlexDirection::Column,
+                                flex_grow: 1.0,
+                                row_gap: Val::Px(12.0),
+                                ..default()
+                            },
+                        ))
+                        .with_children(|parent| {
+                            parent
+                                .spawn((
+                                    Name::new("Speaker Name Plate"),
+                                    SpeakerNamePlate,
+                                    Node {
+                                        align_self: AlignSelf::Start,
+                                        padding: UiRect::axes(Val::Px(12.0), Val::Px(4.0)),
+                                        display: Display::None,
+                                        ..default()
+                                    },
+                                    BackgroundColor(NODE_BACKGROUND),
+                                ))
+                                .with_child((
+                                    Name::new("Speaker Name"),
+                                    SpeakerName,
+                                    Text::default(),
+                                    TextFont::from_font_size(24.0),
+                                    TextColor(HEADER_TEXT),
+                                ));
+                            parent.spawn((
+                                Name::new("Line Text"),
+                                LineText,
+                                Text::default(),
+                                TextFont::from_font_size(24.0),
+                                TextColor(BUTTON_TEXT),
+                            ));
+                            parent.spawn((
+                                Name::new("Option List"),
+                                OptionList,
+                                Node {
+                                    flex_direction: FlexDirection::Column,
+                                    row_gap: Val::Px(6.0),
+                                    ..default()
+                                },
+                            ));
+                        });
+                });
+        });
+}
+
+fn show_dialogue_view(mut root: Single<&mut Visibility, With<DialogueViewRoot>>) {
+    **root = Visibility::Inherited;
+}
+
+fn hide_dialogue_view(
+    mut commands: Commands,
+    mut root: Single<&mut Visibility, With<DialogueViewRoot>>,
+    option_list: Single<Entity, With<OptionList>>,
+) {
+    **root = Visibility::Hidden;
+    commands.insert_resource(Typewriter::default());
+    commands.remove_resource::<OptionSelection>();
+    commands.entity(*option_list).despawn_descendants();
+}
+
+/// The metadata of a line that changes how it is presented.
+#[derive(Debug, Clone, Default)]
+struct LineMetadata {
+    last_line: bool,
+    portrait: Option<String>,
+    speed: Option<f32>,
+}
+
+impl LineMetadata {
+    fn parse(metadata: &[String]) -> Self {
+        let mut parsed = Self::default();
+        for tag in metadata {
+            let tag = tag.trim_start_matches('#');
+            if tag == "lastline" {
+                parsed.last_line = true;
+            } else if let Some(portrait) = tag.strip_prefix("portrait:") {
+                parsed.portrait = Some(portrait.to_string());
+            } else if let Some(speed) = tag.strip_prefix("speed:") {
+                match speed.parse() {
+                    Ok(speed) => parsed.speed = Some(speed),
+                    Err(err) => warn!("Invalid #speed tag {tag:?}: {err}"),
+                }
+            }
+        }
+        parsed
+    }
+}
@@ -0,0 +1,189 @@
+//! Presents the options of a dialogue as a numbered list.
+
+use bevy::prelude::*;
+use bevy_enhanced_input::prelude::*;
+use bevy_yarnspinner::{events::PresentOptionsEvent, prelude::*};
+
+use crate::{
+    gameplay::player::dialogue_input::{AdvanceDialogue, NavigateOptions, SelectOption},
+    theme::{
+        interaction::{InteractionPalette, OnPress},
+        palette::*,
+    },
+};
+
+use super::{DialogueViewSet, LineText, OptionList, SpeakerNamePlate, line::Typewriter};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(
+        Update,
+        (
+            present_options,
+            highlight_selected_option.run_if(resource_exists_and_changed::<OptionSelection>),
+        )
+            .chain()
+            .in_set(DialogueViewSet),
+    );
+    app.add_observer(select_option_by_number);
+    app.add_observer(navigate_options);
+    app.add_observer(confirm_selected_option);
+}
+
+/// The options that are currently presented. Only exists while the player has to pick one.
+#[derive(Resource, Debug)]
+pub(super) struct OptionSelection {
+    options: Vec<OptionId>,
+    selected: usize,
+}
+
+/// A button for the option with the given index in [`OptionSelection`].
+#[derive(Component, Debug)]
+struct OptionButton(usize);
+
+fn present_options(
+    mut commands: Commands,
+    mut present_options_events: EventReader<PresentOptionsEvent>,
+    option_list: Single<Entity, With<OptionList>>,
+    typewriter: Res<Typewriter>,
+    mut line_text: Single<&mut Text, With<LineText>>,
+    mut name_plate: Single<&mut Node, With<SpeakerNamePlate>>,
+) {
+    for event in present_options_events.read() {
+        if !typewriter.is_last_line {
+            line_text.0.clear();
+            name_plate.display = Display::None;
+        }
+        let options: Vec<_> = event
+            .options
+            .iter()
+            .filter(|option| option.is_available)
+            .collect();
+        commands.entity(*option_list).despawn_descendants();
+        commands.entity(*option_list).with_children(|parent| {
+            for (index, option) in options.iter().enumerate() {
+                let text = option.line.text_without_character_name();
+                parent
+                    .spawn((
+                        Name::new("Dialogue Option"),
+                        OptionButton(index),
+                        Button,
+                        Node {
+                            padding: UiRect::axes(Val::Px(12.0), Val::Px(6.0)),
+                            ..default()
+                        },
+                        BackgroundColor(NODE_BACKGROUND),
+                        InteractionPalette {
+                            none: NODE_BACKGROUND,
+                            hovered: BUTTON_HOVERED_BACKGROUND,
+                            pressed: BUTTON_PRESSED_BACKGROUND,
+                        },
+                    ))
+                    .with_child((
+                        Text(format!("{}. {text}", index + 1)),
+                        TextFont::from_font_size(22.0),
+                        TextColor(BUTTON_TEXT),
+                    ))
+                    .observe(select_option_on_click);
+            }
+        });
+        commands.insert_resource(OptionSelection {
+            options: options.iter().map(|option| option.id).collect(),
+            selected: 0,
+        });
+    }
+}
+
+fn highlight_selected_option(
+    selection: Res<OptionSelection>,
+    mut q_button: Query<(&OptionButton, &Interaction, &mut BackgroundColor)>,
+) {
+    for (button, interaction, mut background) in &mut q_button {
+        if *interaction != Interaction::None {
+            // The mouse takes precedence.
+            continue;
+        }
+        background.0 = if button.0 == selection.selected {
+            BUTTON_HOVERED_BACKGROUND
+        } else {
+            NODE_BACKGROUND
+        };
+    }
+}
+
+fn select_option_by_number(
+    trigger: Trigger<Started<SelectOption>>,
+    mut commands: Commands,
+    selection: Option<Res<OptionSelection>>,
+) {
+    let Some(selection) = selection else {
+        return;
+    };
+    // The number keys are bound to 1.0 through 9.0.
+    let index = trigger.value.round() as usize;
+    if let Some(index) = index
+        .checked_sub(1)
+        .filter(|&i| i < selection.options.len())
+    {
+        commands.run_system_cached_with(choose_option, index);
+    }
+}
+
+fn navigate_options(
+    trigger: Trigger<Started<NavigateOptions>>,
+    selection: Option<ResMut<OptionSelection>>,
+) {
+    let Some(mut selection) = selection else {
+        return;
+    };
+    let count = selection.options.len();
+    if count == 0 {
+        return;
+    }
+    selection.selected = if trigger.value > 0.0 {
+        (selection.selected + count - 1) % count
+    } else {
+        (selection.selected + 1) % count
+    };
+}
+
+fn confirm_selected_option(
+    _trigger: Trigger<Started<AdvanceDialogue>>,
+    mut commands: Commands,
+    selection: Option<Res<OptionSelection>>,
+) {
+    if let Some(selection) = selection {
+        commands.run_system_cached_with(choose_option, selection.selected);
+    }
+}
+
+fn select_option_on_click(
+    trigger: Trigger<OnPress>,
+    mut commands: Commands,
+    q_button: Query<&OptionButton>,
+) {
+    if let Ok(button) = q_button.get(trigger.entity()) {
+        commands.run_system_cached_with(choose_option, button.0);
+    }
+}
+
+fn choose_option(
+    In(index): In<usize>,
+    mut commands: Commands,
+    selection: Option<Res<OptionSelection>>,
+    option_list: Single<Entity, With<OptionList>>,
+    mut dialogue_runner: Single<&mut DialogueRunner>,
+) {
+    // Several inputs may pick an option in the same frame.
+    let Some(selection) = selection else {
+        return;
+    };
+    let Some(&option) = selection.options.get(index) else {
+        return;
+    };
+    if let Err(err) = dialogue_runner.select_option(option) {
+        error!("Failed to select dialogue option: {err}");
+        return;
+    }
+    commands.remove_resource::<OptionSelection>();
+    commands.entity(*option_list).despawn_descendants();
+}
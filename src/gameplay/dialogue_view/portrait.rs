@@ -0,0 +1,59 @@
+//! Shows a portrait of the speaker next to their lines.
+
+use bevy::{prelude::*, utils::HashMap};
+
+use super::Portrait;
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<CharacterPortraits>();
+    app.add_observer(show_portrait);
+}
+
+/// The portraits of the characters in our dialogue, as pairs of speaker names and image paths.
+/// The speaker name is what is written before the colon of a line in Yarn.
+/// Speakers without a portrait are shown without one.
+const PORTRAITS: &[(&str, &str)] = &[("The Follower", "images/ducky.png")];
+
+#[derive(Resource, Debug)]
+struct CharacterPortraits(HashMap<&'static str, Handle<Image>>);
+
+impl FromWorld for CharacterPortraits {
+    fn from_world(world: &mut World) -> Self {
+        let assets = world.resource::<AssetServer>();
+        Self(
+            PORTRAITS
+                .iter()
+                .map(|&(speaker, path)| (speaker, assets.load(path)))
+                .collect(),
+        )
+    }
+}
+
+#[derive(Event, Debug)]
+pub(super) struct ShowPortrait {
+    pub(super) speaker: Option<String>,
+    /// A path that overrides the speaker's portrait for this line.
+    pub(super) portrait: Option<String>,
+}
+
+fn show_portrait(
+    trigger: Trigger<ShowPortrait>,
+    portraits: Res<CharacterPortraits>,
+    assets: Res<AssetServer>,
+    portrait_node: Single<(&mut ImageNode, &mut Node), With<Portrait>>,
+) {
+    let event = trigger.event();
+    let image = match (&event.portrait, &event.speaker) {
+        (Some(path), _) => Some(assets.load(path)),
+        (None, Some(speaker)) => portraits.0.get(speaker.as_str()).cloned(),
+        (None, None) => None,
+    };
+    let (mut image_node, mut node) = portrait_node.into_inner();
+    match image {
+        Some(image) => {
+            image_node.image = image;
+            node.display = Display::Flex;
+        }
+        None => node.display = Display::None,
+    }
+}
@@ -0,0 +1,95 @@
+//! Doors that slide open when a dialogue script tells them to.
+
+use avian3d::prelude::*;
+use bevy::prelude::*;
+use bevy_trenchbroom::prelude::*;
+
+use crate::{AppSet, screens::Screen, third_party::bevy_trenchbroom::target::TargetName};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<(Door, DoorState)>();
+    app.add_systems(
+        Update,
+        move_doors
+            .run_if(in_state(Screen::Gameplay))
+            .in_set(AppSet::Update),
+    );
+}
+
+/// A brush that slides upwards when opened through its `targetname`.
+#[derive(SolidClass, Component, Debug, Clone, Reflect)]
+#[reflect(Component, Default, Debug)]
+#[base(TargetName)]
+#[require(RigidBody(kinematic), DoorState)]
+#[geometry(GeometryProvider::new().convex_collider().smooth_by_default_angle())]
+// In Wasm, TrenchBroom classes are not automatically registered.
+// So, we need to manually register the class in `src/third_party/bevy_trenchbroom/mod.rs`.
+pub(crate) struct Door {
+    /// How far the door moves up when opened, in meters.
+    pub(crate) lift: f32,
+    /// How fast the door moves, in meters per second.
+    pub(crate) speed: f32,
+}
+
+impl Default for Door {
+    fn default() -> Self {
+        Self {
+            lift: 2.5,
+            speed: 2.0,
+        }
+    }
+}
+
+fn kinematic() -> RigidBody {
+    RigidBody::Kinematic
+}
+
+#[derive(Component, Debug, Default, Reflect)]
+#[reflect(Component, Default, Debug)]
+pub(crate) struct DoorState {
+    pub(crate) open: bool,
+    /// Where the door was placed in the map.
+    closed_translation: Option<Vec3>,
+}
+
+impl DoorState {
+    /// Opens the door without sliding it there, e.g. when loading a save game.
+    pub(crate) fn open_instantly(&mut self, door: &Door, transform: &mut Transform) {
+        let closed = *self.closed_translation.get_or_insert(transform.translation);
+        self.open = true;
+        transform.translation = closed + Vec3::Y * door.lift;
+    }
+}
+
+/// Opens all doors with the given `targetname`. Returns whether any door was found.
+pub(crate) fn open_doors<'a>(
+    targetname: &str,
+    doors: impl IntoIterator<Item = (&'a TargetName, Mut<'a, DoorState>)>,
+) -> bool {
+    let mut found = false;
+    for (name, mut state) in doors {
+        if name.targetname == targetname {
+            state.open = true;
+            found = true;
+        }
+    }
+    found
+}
+
+fn move_doors(time: Res<Time>, mut q_door: Query<(&Door, &mut DoorState, &mut Transform)>) {
+    for (door, mut state, mut transform) in &mut q_door {
+        let closed = *state
+            .closed_translation
+            .get_or_insert(transform.translation);
+        let target = if state.open {
+            closed + Vec3::Y * door.lift
+        } else {
+            closed
+        };
+        if transform.translation != target {
+            transform.translation = transform
+                .translation
+                .move_towards(target, door.speed * time.delta_secs());
+        }
+    }
+}
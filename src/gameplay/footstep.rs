@@ -0,0 +1,101 @@
+//! Footsteps of any Tnua character, timed by the distance walked rather than by a clock,
+//! so that they keep up with the character at every speed.
+//!
+//! Observe [`Footstep`] to react to them, e.g. with sounds, particles or a camera bob.
+
+use avian3d::prelude::*;
+use bevy::prelude::*;
+use bevy_tnua::prelude::*;
+
+use crate::{AppSet, screens::Screen};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<Strides>();
+    app.add_systems(
+        Update,
+        take_strides
+            .run_if(in_state(Screen::Gameplay))
+            .in_set(AppSet::Update),
+    );
+}
+
+/// Triggered on a character every time it completes a stride.
+#[derive(Debug, Event, Clone, Copy)]
+pub(crate) struct Footstep;
+
+/// Below this horizontal speed (in m/s), a character is standing rather than walking.
+/// Keeps the spring of the character controller from slowly adding up to a step.
+const MIN_WALKING_SPEED: f32 = 1.0;
+
+/// Accumulates the horizontal distance a character walks on the ground
+/// and triggers a [`Footstep`] every [`Strides::length`] meters.
+#[derive(Debug, Component, Reflect, Clone, Copy)]
+#[reflect(Component, Debug)]
+pub(crate) struct Strides {
+    /// In meters.
+    pub(crate) length: f32,
+    distance: f32,
+}
+
+impl Strides {
+    pub(crate) fn new(length: f32) -> Self {
+        Self {
+            length,
+            distance: 0.0,
+        }
+    }
+
+    /// Walks `distance` meters and returns whether that completed a stride.
+    fn walk(&mut self, distance: f32) -> bool {
+        self.distance += distance;
+        if self.distance < self.length {
+            return false;
+        }
+        // Even at absurd speeds, one footstep per frame is plenty.
+        self.distance %= self.length;
+        true
+    }
+}
+
+fn take_strides(
+    mut commands: Commands,
+    mut q_character: Query<(Entity, &TnuaController, &LinearVelocity, &mut Strides)>,
+    time: Res<Time>,
+) {
+    for (entity, controller, linear_velocity, mut strides) in &mut q_character {
+        if controller.is_airborne().unwrap_or(true) {
+            continue;
+        }
+        let speed = linear_velocity.xz().length();
+        if speed < MIN_WALKING_SPEED {
+            continue;
+        }
+        if strides.walk(speed * time.delta_secs()) {
+            commands.trigger_targets(Footstep, entity);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strides_accumulate_until_a_full_stride() {
+        let mut strides = Strides::new(1.0);
+        assert!(!strides.walk(0.4));
+        assert!(!strides.walk(0.4));
+        assert!(strides.walk(0.4));
+        // The rest of the last stride counts towards the next one.
+        assert!(!strides.walk(0.7));
+        assert!(strides.walk(0.2));
+    }
+
+    #[test]
+    fn long_distances_take_a_single_stride() {
+        let mut strides = Strides::new(1.0);
+        assert!(strides.walk(10.5));
+        assert!(!strides.walk(0.4));
+        assert!(strides.walk(0.2));
+    }
+}
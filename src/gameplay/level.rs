@@ -1,28 +1,148 @@
-//! Spawn the main level.
+//! Spawn the current level and move between levels.
 
-use bevy::{prelude::*, scene::SceneInstanceReady};
+use avian3d::prelude::*;
+use bevy::{
+    ecs::world::Command,
+    image::{ImageAddressMode, ImageLoaderSettings, ImageSampler, ImageSamplerDescriptor},
+    prelude::*,
+};
+use bevy_trenchbroom::{bevy_materialize::GenericMaterial, prelude::*};
+use serde::{Deserialize, Serialize};
 
 use crate::{
-    asset_tracking::LoadResource, props::*, screens::Screen,
-    third_party::bevy_trenchbroom::GetTrenchbroomModelPath as _,
+    AppSet,
+    asset_tracking::LoadResource,
+    screens::Screen,
+    third_party::bevy_trenchbroom::{
+        hidden,
+        preload::{ClassPreloads, MapEntity},
+    },
 };
 
+use super::player::Player;
+
 pub(super) fn plugin(app: &mut App) {
-    app.load_resource::<LevelAssets>();
-    app.register_type::<Level>();
+    app.init_asset::<LevelAssets>();
+    app.init_resource::<LevelRegistry>();
+    app.register_type::<(Level, LevelId, CurrentLevel, LevelExit)>();
+    app.add_systems(OnEnter(Screen::Loading), load_current_level);
+    app.add_systems(OnEnter(Screen::Title), forget_current_level);
+    app.add_systems(
+        Update,
+        exit_level
+            .run_if(in_state(Screen::Gameplay).and(on_event::<CollisionStarted>))
+            .in_set(AppSet::Update),
+    );
 }
 
-/// A [`Command`] to spawn the level.
-/// Functions that accept only `&mut World` as their parameter implement [`Command`].
-/// We use this style when a command requires no configuration.
-pub(crate) fn spawn_level(world: &mut World) {
-    let assets = world.resource::<LevelAssets>();
-    world.spawn((
-        Name::new("Level"),
-        SceneRoot(assets.level.clone()),
-        StateScoped(Screen::Gameplay),
-        Level,
-    ));
+/// Identifies a level in the [`LevelRegistry`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Reflect, Serialize, Deserialize)]
+#[reflect(Debug, PartialEq, Hash)]
+pub(crate) struct LevelId(pub(crate) String);
+
+impl Default for LevelId {
+    fn default() -> Self {
+        Self("foxtrot".to_string())
+    }
+}
+
+/// Everything needed to load and spawn a level.
+#[derive(Debug, Clone)]
+pub(crate) struct LevelInfo {
+    pub(crate) id: LevelId,
+    pub(crate) display_name: String,
+    /// The path to the TrenchBroom map, relative to the assets directory.
+    pub(crate) map_path: String,
+}
+
+impl LevelInfo {
+    /// The entities and textures of the level's map, generated by the build script.
+    fn manifest(&self) -> Option<&'static MapManifest> {
+        let manifest = MAP_MANIFESTS
+            .iter()
+            .find(|manifest| manifest.path == self.map_path);
+        if manifest.is_none() {
+            warn!(
+                "No manifest found for {}, its assets will load during gameplay.",
+                self.map_path
+            );
+        }
+        manifest
+    }
+
+    /// Identifies the current version of the level's map.
+    pub(crate) fn map_hash(&self) -> Option<u64> {
+        self.manifest().map(|manifest| manifest.hash)
+    }
+}
+
+/// What a map contains, as far as preloading is concerned.
+struct MapManifest {
+    /// The path to the map, relative to the assets directory.
+    path: &'static str,
+    /// Changes whenever the map file does.
+    hash: u64,
+    entities: &'static [MapEntity],
+    /// The textures used by the map's brushes, relative to the assets directory.
+    /// These are either generic materials or plain images.
+    textures: &'static [&'static str],
+}
+
+include!(concat!(env!("OUT_DIR"), "/map_manifest.rs"));
+
+/// All levels that can be played.
+#[derive(Resource, Debug)]
+pub(crate) struct LevelRegistry(Vec<LevelInfo>);
+
+impl Default for LevelRegistry {
+    fn default() -> Self {
+        Self(vec![LevelInfo {
+            id: LevelId::default(),
+            display_name: "Foxtrot".to_string(),
+            //  Run ./scripts/compile_maps.sh and change .map to .bsp when we're done prototyping and want some extra performance
+            map_path: "maps/foxtrot/foxtrot.map".to_string(),
+        }])
+    }
+}
+
+impl LevelRegistry {
+    pub(crate) fn register(&mut self, level: LevelInfo) {
+        self.0.push(level);
+    }
+
+    pub(crate) fn get(&self, id: &LevelId) -> Option<&LevelInfo> {
+        self.0.iter().find(|level| &level.id == id)
+    }
+}
+
+/// The level that is being loaded or played.
+/// Only exists while a level has been chosen, i.e. not on the title screen.
+#[derive(Resource, Debug, Clone, Default, Reflect)]
+#[reflect(Resource)]
+pub(crate) struct CurrentLevel(pub(crate) LevelId);
+
+/// A [`Command`] to spawn a level.
+/// Its assets must have been loaded through [`Screen::Loading`] beforehand.
+#[derive(Debug)]
+pub(crate) struct SpawnLevel(pub(crate) LevelId);
+
+impl Command for SpawnLevel {
+    fn apply(self, world: &mut World) {
+        let Some(assets) = world.get_resource::<LevelAssets>() else {
+            error!("Tried to spawn level {:?} before loading it", self.0);
+            return;
+        };
+        if assets.id != self.0 {
+            error!(
+                "Tried to spawn level {:?}, but level {:?} is loaded",
+                self.0, assets.id
+            );
+            return;
+        }
+        let name = Name::new(format!("Level {}", self.0.0));
+        let scene = assets.level.clone();
+        world.spawn((name, SceneRoot(scene), StateScoped(Screen::Gameplay), Level));
+    }
 }
 
 #[derive(Debug, Component, Reflect)]
@@ -31,31 +151,126 @@ pub(crate) struct Level;
 
 #[derive(Resource, Asset, Clone, TypePath)]
 struct LevelAssets {
+    id: LevelId,
     #[dependency]
-    pub(crate) level: Handle<Scene>,
+    level: Handle<Scene>,
     #[dependency]
-    pub(crate) props: Vec<UntypedHandle>,
+    entities: Vec<UntypedHandle>,
+    #[dependency]
+    textures: Vec<UntypedHandle>,
 }
 
 impl FromWorld for LevelAssets {
     fn from_world(world: &mut World) -> Self {
+        let mut id = world.resource::<CurrentLevel>().0.clone();
+        let registry = world.resource::<LevelRegistry>();
+        let level = match registry.get(&id) {
+            Some(level) => level.clone(),
+            // E.g. a save made in a dev build in a level that only exists in dev builds.
+            None => {
+                error!("Level {id:?} is not registered, loading the default level instead");
+                id = LevelId::default();
+                let level = registry
+                    .get(&id)
+                    .cloned()
+                    .expect("The default level is always registered");
+                world.insert_resource(CurrentLevel(id.clone()));
+                level
+            }
+        };
         let assets = world.resource::<AssetServer>();
-        Self {
-            //  Run ./scripts/compile_maps.sh and change .map to .bsp when we're done prototyping and want some extra performance
-            level: assets.load("maps/foxtrot/foxtrot.map#Scene"),
-            // We preload all props used in the level here. The template is setup such that we get a helpful warning if we miss one.
-            props: [
-                Chair::scene_path(),
-                Table::scene_path(),
-                Grate::scene_path(),
-                Bookshelf::scene_path(),
-                LampSitting::scene_path(),
-                Crate::scene_path(),
-            ]
+        // We preload the assets of all entities and brush textures in the level here.
+        // Entities without registered preloads are assumed to need no assets of their own,
+        // e.g. doors and level exits, which are drawn with the world textures.
+        let manifest = level.manifest();
+        let entities = world.resource::<ClassPreloads>().load(
+            manifest.into_iter().flat_map(|manifest| manifest.entities),
+            assets,
+        );
+        let textures = manifest
             .into_iter()
-            .map(|path| assets.load::<Scene>(path).untyped())
-            .chain(BurningLogs::preload(assets))
-            .collect(),
+            .flat_map(|manifest| manifest.textures)
+            .map(|path| preload_texture(path, assets))
+            .collect();
+        Self {
+            id,
+            level: assets.load(format!("{}#Scene", level.map_path)),
+            entities,
+            textures,
+        }
+    }
+}
+
+/// Starts loading a world texture with the settings the map loader uses,
+/// since whoever loads a path first decides its settings.
+fn preload_texture(path: &str, assets: &AssetServer) -> UntypedHandle {
+    if path.ends_with(".material.toml") {
+        assets.load::<GenericMaterial>(path.to_string()).untyped()
+    } else {
+        // World textures tile across brush faces.
+        assets
+            .load_with_settings(path.to_string(), |settings: &mut ImageLoaderSettings| {
+                settings.sampler = ImageSampler::Descriptor(ImageSamplerDescriptor {
+                    address_mode_u: ImageAddressMode::Repeat,
+                    address_mode_v: ImageAddressMode::Repeat,
+                    address_mode_w: ImageAddressMode::Repeat,
+                    ..default()
+                });
+            })
+            .untyped()
+    }
+}
+
+/// Queues the assets of the [`CurrentLevel`], if any, for the loading screen.
+fn load_current_level(world: &mut World) {
+    let Some(current_level) = world.get_resource::<CurrentLevel>() else {
+        return;
+    };
+    let is_loaded = world
+        .get_resource::<LevelAssets>()
+        .is_some_and(|assets| assets.id == current_level.0);
+    if is_loaded {
+        return;
+    }
+    world.remove_resource::<LevelAssets>();
+    world.load_resource::<LevelAssets>();
+}
+
+fn forget_current_level(mut commands: Commands) {
+    commands.remove_resource::<CurrentLevel>();
+}
+
+/// A trigger volume that moves the player to another level when entered.
+#[derive(SolidClass, Component, Debug, Clone, Default, Reflect)]
+#[reflect(Component, Default, Debug)]
+#[require(Sensor, Visibility(hidden))]
+#[geometry(GeometryProvider::new().convex_collider())]
+pub(crate) struct LevelExit {
+    /// The [`LevelId`] of the level to move to.
+    pub(crate) target_level: String,
+}
+
+fn exit_level(
+    mut collision_started: EventReader<CollisionStarted>,
+    q_level_exit: Query<&LevelExit>,
+    q_player: Query<(), With<Player>>,
+    registry: Res<LevelRegistry>,
+    mut commands: Commands,
+    mut next_screen: ResMut<NextState<Screen>>,
+) {
+    for CollisionStarted(a, b) in collision_started.read() {
+        let level_exit = match (q_level_exit.get(*a), q_level_exit.get(*b)) {
+            (Ok(level_exit), _) if q_player.contains(*b) => level_exit,
+            (_, Ok(level_exit)) if q_player.contains(*a) => level_exit,
+            _ => continue,
+        };
+        let target = LevelId(level_exit.target_level.clone());
+        if registry.get(&target).is_none() {
+            error!("Level exit leads to unknown level {target:?}");
+            continue;
         }
+        commands.insert_resource(CurrentLevel(target));
+        next_screen.set(Screen::Loading);
+        return;
     }
 }
@@ -0,0 +1,125 @@
+//! The sounds that each [`PhysicsMaterial`] makes, both when something hits it and when someone walks on it.
+//!
+//! We only have recordings of footsteps on rock so far, so every material plays those, and impacts reuse the landing sounds.
+//! Until we have recordings for the other materials, the material only changes how fast, and thus how high, they play.
+
+use bevy::{asset::VisitAssetDependencies, prelude::*};
+use bevy_shuffle_bag::ShuffleBag;
+
+use crate::{asset_tracking::LoadResource, third_party::avian3d::PhysicsMaterial};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<MaterialSoundAssets>();
+    app.load_resource::<MaterialSoundAssets>();
+}
+
+/// The sounds of a single material.
+#[derive(Clone, Reflect, VisitAssetDependencies)]
+pub(crate) struct MaterialSounds {
+    #[dependency]
+    pub(crate) impacts: ShuffleBag<Handle<AudioSource>>,
+    #[dependency]
+    pub(crate) steps: ShuffleBag<Handle<AudioSource>>,
+    #[dependency]
+    pub(crate) run_steps: ShuffleBag<Handle<AudioSource>>,
+    #[dependency]
+    pub(crate) jump_starts: ShuffleBag<Handle<AudioSource>>,
+    #[dependency]
+    pub(crate) lands: ShuffleBag<Handle<AudioSource>>,
+    /// The playback speed of the sounds.
+    pub(crate) speed: f32,
+}
+
+#[derive(Resource, Asset, Clone, Reflect)]
+#[reflect(Resource)]
+pub(crate) struct MaterialSoundAssets {
+    #[dependency]
+    wood: MaterialSounds,
+    #[dependency]
+    metal: MaterialSounds,
+    #[dependency]
+    stone: MaterialSounds,
+}
+
+impl PhysicsMaterial {
+    pub(crate) fn sounds(self, assets: &mut MaterialSoundAssets) -> &mut MaterialSounds {
+        match self {
+            PhysicsMaterial::Wood => &mut assets.wood,
+            PhysicsMaterial::Metal => &mut assets.metal,
+            PhysicsMaterial::Stone => &mut assets.stone,
+        }
+    }
+}
+
+impl FromWorld for MaterialSoundAssets {
+    fn from_world(world: &mut World) -> Self {
+        let assets = world.resource::<AssetServer>();
+        // The other materials play the rock sounds faster to sound lighter, see the module docs.
+        Self {
+            wood: rock_sounds(assets, 1.2),
+            metal: rock_sounds(assets, 1.5),
+            stone: rock_sounds(assets, 1.0),
+        }
+    }
+}
+
+fn rock_sounds(assets: &AssetServer, speed: f32) -> MaterialSounds {
+    let mut rng = rand::thread_rng();
+    let lands = [
+        assets.load("audio/sound_effects/land/Footsteps_Rock_Jump_Land_01.ogg"),
+        assets.load("audio/sound_effects/land/Footsteps_Rock_Jump_Land_02.ogg"),
+        assets.load("audio/sound_effects/land/Footsteps_Rock_Jump_Land_03.ogg"),
+        assets.load("audio/sound_effects/land/Footsteps_Rock_Jump_Land_04.ogg"),
+        assets.load("audio/sound_effects/land/Footsteps_Rock_Jump_Land_05.ogg"),
+        assets.load("audio/sound_effects/land/Footsteps_Rock_Jump_Land_06.ogg"),
+    ];
+    MaterialSounds {
+        // A prop hitting the floor sounds close enough to someone landing on it.
+        impacts: ShuffleBag::try_new(lands.clone(), &mut rng).unwrap(),
+        steps: ShuffleBag::try_new(
+            [
+                assets.load("audio/sound_effects/step/Footsteps_Rock_Walk_01.ogg"),
+                assets.load("audio/sound_effects/step/Footsteps_Rock_Walk_02.ogg"),
+                assets.load("audio/sound_effects/step/Footsteps_Rock_Walk_03.ogg"),
+                assets.load("audio/sound_effects/step/Footsteps_Rock_Walk_04.ogg"),
+                assets.load("audio/sound_effects/step/Footsteps_Rock_Walk_05.ogg"),
+                assets.load("audio/sound_effects/step/Footsteps_Rock_Walk_06.ogg"),
+                assets.load("audio/sound_effects/step/Footsteps_Rock_Walk_07.ogg"),
+                assets.load("audio/sound_effects/step/Footsteps_Rock_Walk_08.ogg"),
+                assets.load("audio/sound_effects/step/Footsteps_Rock_Walk_09.ogg"),
+            ],
+            &mut rng,
+        )
+        .unwrap(),
+        run_steps: ShuffleBag::try_new(
+            [
+                assets.load("audio/sound_effects/run/Footsteps_Rock_Run_01.ogg"),
+                assets.load("audio/sound_effects/run/Footsteps_Rock_Run_02.ogg"),
+                assets.load("audio/sound_effects/run/Footsteps_Rock_Run_03.ogg"),
+                assets.load("audio/sound_effects/run/Footsteps_Rock_Run_04.ogg"),
+                assets.load("audio/sound_effects/run/Footsteps_Rock_Run_05.ogg"),
+                assets.load("audio/sound_effects/run/Footsteps_Rock_Run_06.ogg"),
+                assets.load("audio/sound_effects/run/Footsteps_Rock_Run_07.ogg"),
+                assets.load("audio/sound_effects/run/Footsteps_Rock_Run_08.ogg"),
+                assets.load("audio/sound_effects/run/Footsteps_Rock_Run_09.ogg"),
+                assets.load("audio/sound_effects/run/Footsteps_Rock_Run_10.ogg"),
+            ],
+            &mut rng,
+        )
+        .unwrap(),
+        jump_starts: ShuffleBag::try_new(
+            [
+                assets.load("audio/sound_effects/jump_start/Footsteps_Rock_Jump_Start_01.ogg"),
+                assets.load("audio/sound_effects/jump_start/Footsteps_Rock_Jump_Start_02.ogg"),
+                assets.load("audio/sound_effects/jump_start/Footsteps_Rock_Jump_Start_03.ogg"),
+                assets.load("audio/sound_effects/jump_start/Footsteps_Rock_Jump_Start_04.ogg"),
+                assets.load("audio/sound_effects/jump_start/Footsteps_Rock_Jump_Start_05.ogg"),
+                assets.load("audio/sound_effects/jump_start/Footsteps_Rock_Jump_Start_06.ogg"),
+            ],
+            &mut rng,
+        )
+        .unwrap(),
+        lands: ShuffleBag::try_new(lands, &mut rng).unwrap(),
+        speed,
+    }
+}
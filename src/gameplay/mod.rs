@@ -7,9 +7,18 @@ use bevy::prelude::*;
 
 mod animation;
 pub(crate) mod crosshair;
+mod dialogue_view;
+pub(crate) mod door;
+pub(crate) mod footstep;
 pub(crate) mod level;
+mod material_sound;
 pub(crate) mod npc;
+mod physics_audio;
 pub(crate) mod player;
+pub(crate) mod room;
+pub(crate) mod save;
+mod scripting;
+pub(crate) mod surface;
 
 pub(super) fn plugin(app: &mut App) {
     app.add_plugins((
@@ -18,5 +27,14 @@ pub(super) fn plugin(app: &mut App) {
         level::plugin,
         crosshair::plugin,
         npc::plugin,
+        save::plugin,
+        dialogue_view::plugin,
+        door::plugin,
+        room::plugin,
+        scripting::plugin,
+        material_sound::plugin,
+        physics_audio::plugin,
+        surface::plugin,
+        footstep::plugin,
     ));
 }
@@ -8,15 +8,23 @@ use bevy_landmass::{
 };
 use bevy_tnua::prelude::*;
 
-use crate::{gameplay::player::PlayerLandmassCharacter, screens::Screen};
+use crate::{
+    gameplay::player::{Player, PlayerLandmassCharacter},
+    screens::Screen,
+};
 
-use super::{NPC_FLOAT_HEIGHT, NPC_RADIUS, Npc};
+use super::{
+    Npc,
+    behaviour::{FLEE_DISTANCE, NpcBehaviour, NpcBrain},
+    patrol::Patrol,
+};
 
 pub(super) fn plugin(app: &mut App) {
     app.add_systems(
         RunFixedMainLoop,
         (
             setup_npc_agent.param_warn_once(),
+            update_agent_target.param_warn_once(),
             sync_agent_velocity,
             set_controller_velocity,
         )
@@ -29,24 +37,23 @@ pub(super) fn plugin(app: &mut App) {
 
 fn setup_npc_agent(
     mut commands: Commands,
-    q_uninitialized: Query<Entity, (With<Npc>, Without<NpcAgent>)>,
-    player: Single<&PlayerLandmassCharacter>,
+    q_uninitialized: Query<(Entity, &Npc), Without<NpcAgent>>,
     archipelago: Single<Entity, With<Archipelago3d>>,
 ) {
-    for entity in q_uninitialized.iter() {
+    for (entity, npc) in q_uninitialized.iter() {
         let agent = commands
             .spawn((
-                Transform::from_translation(Vec3::new(0.0, -NPC_FLOAT_HEIGHT, 0.0)),
+                Transform::from_translation(Vec3::new(0.0, -npc.float_height, 0.0)),
                 Agent3dBundle {
                     agent: Default::default(),
                     settings: AgentSettings {
-                        radius: NPC_RADIUS,
-                        desired_speed: 7.0,
-                        max_speed: 8.0,
+                        radius: npc.radius,
+                        desired_speed: npc.desired_speed,
+                        max_speed: npc.max_speed,
                     },
                     archipelago_ref: ArchipelagoRef3d::new(*archipelago),
                 },
-                AgentTarget3d::Entity(player.0),
+                AgentTarget3d::None,
                 TargetReachedCondition::Distance(Some(2.0)),
             ))
             .set_parent(entity)
@@ -58,21 +65,69 @@ fn setup_npc_agent(
 #[derive(Component)]
 struct NpcAgent(Entity);
 
+/// Points the navigation agent to wherever the NPC's current behaviour wants to go.
+fn update_agent_target(
+    q_npc: Query<(&NpcAgent, &NpcBrain, &Patrol, &GlobalTransform)>,
+    mut q_agent_target: Query<&mut AgentTarget3d>,
+    q_transform: Query<&GlobalTransform>,
+    player: Single<(&GlobalTransform, &PlayerLandmassCharacter), With<Player>>,
+) {
+    let (player_transform, player_character) = player.into_inner();
+    for (npc_agent, brain, patrol, transform) in &q_npc {
+        let Ok(mut agent_target) = q_agent_target.get_mut(npc_agent.0) else {
+            continue;
+        };
+        *agent_target = match brain.behaviour {
+            NpcBehaviour::Idle | NpcBehaviour::Converse => AgentTarget3d::None,
+            NpcBehaviour::Follow => AgentTarget3d::Entity(player_character.0),
+            NpcBehaviour::Patrol if patrol.is_waiting() => AgentTarget3d::None,
+            NpcBehaviour::Patrol => patrol
+                .corner
+                .and_then(|corner| q_transform.get(corner).ok())
+                .map_or(AgentTarget3d::None, |corner| {
+                    AgentTarget3d::Point(corner.translation())
+                }),
+            NpcBehaviour::Flee => {
+                let away = (transform.translation() - player_transform.translation())
+                    .with_y(0.0)
+                    .normalize_or_zero();
+                AgentTarget3d::Point(transform.translation() + away * 2.0 * FLEE_DISTANCE)
+            }
+        };
+    }
+}
+
 /// Use the desired velocity as the agent's velocity.
+/// While conversing, the NPC instead stands still and faces the player.
 fn set_controller_velocity(
-    mut agent_query: Query<(&mut TnuaController, &NpcAgent)>,
+    mut agent_query: Query<(
+        &mut TnuaController,
+        &Npc,
+        &NpcAgent,
+        &NpcBrain,
+        &GlobalTransform,
+    )>,
     desired_velocity_query: Query<&LandmassAgentDesiredVelocity>,
+    player: Option<Single<&GlobalTransform, With<Player>>>,
 ) {
-    for (mut controller, npc_agent) in agent_query.iter_mut() {
+    for (mut controller, npc, npc_agent, brain, transform) in agent_query.iter_mut() {
         let Ok(desired_velocity) = desired_velocity_query.get(npc_agent.0) else {
             continue;
         };
-        let velocity = desired_velocity.velocity();
-        let forward = Dir3::try_from(velocity).ok();
+        let (velocity, forward) = match (brain.behaviour, player.as_deref()) {
+            (NpcBehaviour::Converse, Some(player)) => {
+                let to_player = (player.translation() - transform.translation()).with_y(0.0);
+                (Vec3::ZERO, Dir3::try_from(to_player).ok())
+            }
+            _ => {
+                let velocity = desired_velocity.velocity();
+                (velocity, Dir3::try_from(velocity).ok())
+            }
+        };
         controller.basis(TnuaBuiltinWalk {
             desired_velocity: velocity,
             desired_forward: forward,
-            float_height: NPC_FLOAT_HEIGHT,
+            float_height: npc.float_height,
             spring_strength: 1000.0,
             ..default()
         });
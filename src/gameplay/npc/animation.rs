@@ -5,7 +5,7 @@ use bevy_tnua::{TnuaAnimatingState, TnuaAnimatingStateDirective, prelude::*};
 
 use crate::{AppSet, gameplay::animation::AnimationPlayerLink, screens::Screen};
 
-use super::assets::NpcAssets;
+use super::Npc;
 
 pub(super) fn plugin(app: &mut App) {
     app.register_type::<NpcAnimations>();
@@ -27,18 +27,19 @@ struct NpcAnimations {
 
 pub(crate) fn setup_npc_animations(
     trigger: Trigger<OnAdd, AnimationPlayerLink>,
-    q_anim_player_link: Query<&AnimationPlayerLink>,
+    q_npc: Query<(&Npc, &AnimationPlayerLink)>,
     mut commands: Commands,
-    assets: Res<NpcAssets>,
+    assets: Res<AssetServer>,
     mut graphs: ResMut<Assets<AnimationGraph>>,
 ) {
-    let anim_player = q_anim_player_link.get(trigger.entity()).unwrap().0;
+    let (npc, anim_player_link) = q_npc.get(trigger.entity()).unwrap();
+    let anim_player = anim_player_link.0;
 
-    let (graph, indices) = AnimationGraph::from_clips([
-        assets.run_animation.clone(),
-        assets.idle_animation.clone(),
-        assets.walk_animation.clone(),
-    ]);
+    // For the default model, these are the handles preloaded by `NpcAssets`.
+    let (graph, indices) =
+        AnimationGraph::from_clips([0, 1, 2].map(|index| {
+            assets.load(GltfAssetLabel::Animation(index).from_asset(npc.model.clone()))
+        }));
     let [run_index, idle_index, walk_index] = indices.as_slice() else {
         unreachable!()
     };
@@ -1,8 +1,12 @@
 use bevy::prelude::*;
-use bevy_shuffle_bag::ShuffleBag;
+use bevy_trenchbroom::class::QuakeClass as _;
 
 use crate::{
-    asset_tracking::LoadResource, third_party::bevy_trenchbroom::GetTrenchbroomModelPath as _,
+    asset_tracking::LoadResource,
+    third_party::bevy_trenchbroom::{
+        GetTrenchbroomModelPath as _,
+        preload::{ClassPreloads, MapEntity},
+    },
 };
 
 use super::Npc;
@@ -10,8 +14,10 @@ use super::Npc;
 pub(super) fn plugin(app: &mut App) {
     app.register_type::<NpcAssets>();
     app.load_resource::<NpcAssets>();
+    app.add_systems(Startup, register_preloads);
 }
 
+/// The assets of the default NPC model. NPCs with a custom `model` load theirs when spawned.
 #[derive(Resource, Asset, Clone, Reflect)]
 #[reflect(Resource)]
 pub(crate) struct NpcAssets {
@@ -23,35 +29,40 @@ pub(crate) struct NpcAssets {
     pub(crate) walk_animation: Handle<AnimationClip>,
     #[dependency]
     pub(crate) run_animation: Handle<AnimationClip>,
-    #[dependency]
-    pub(crate) steps: ShuffleBag<Handle<AudioSource>>,
 }
 
 impl FromWorld for NpcAssets {
     fn from_world(world: &mut World) -> Self {
         let assets = world.resource::<AssetServer>();
-        let mut rng = rand::thread_rng();
         Self {
             model: assets.load(Npc::scene_path()),
             run_animation: assets.load(Npc::animation_path(0)),
             idle_animation: assets.load(Npc::animation_path(1)),
             walk_animation: assets.load(Npc::animation_path(2)),
-            steps: ShuffleBag::try_new(
-                [
-                    assets.load("audio/sound_effects/run/Footsteps_Rock_Run_01.ogg"),
-                    assets.load("audio/sound_effects/run/Footsteps_Rock_Run_02.ogg"),
-                    assets.load("audio/sound_effects/run/Footsteps_Rock_Run_03.ogg"),
-                    assets.load("audio/sound_effects/run/Footsteps_Rock_Run_04.ogg"),
-                    assets.load("audio/sound_effects/run/Footsteps_Rock_Run_05.ogg"),
-                    assets.load("audio/sound_effects/run/Footsteps_Rock_Run_06.ogg"),
-                    assets.load("audio/sound_effects/run/Footsteps_Rock_Run_07.ogg"),
-                    assets.load("audio/sound_effects/run/Footsteps_Rock_Run_08.ogg"),
-                    assets.load("audio/sound_effects/run/Footsteps_Rock_Run_09.ogg"),
-                    assets.load("audio/sound_effects/run/Footsteps_Rock_Run_10.ogg"),
-                ],
-                &mut rng,
-            )
-            .unwrap(),
         }
     }
 }
+
+/// NPCs with a custom `model` need it loaded before the level spawns, just like the default one.
+/// Their step sounds come from the materials they walk on, which are always loaded.
+fn register_preloads(mut preloads: ResMut<ClassPreloads>) {
+    preloads.insert(
+        Npc::CLASS_INFO.name,
+        |entity: &MapEntity, assets: &AssetServer| {
+            let Some(model) = entity.property("model") else {
+                // Already loaded by `NpcAssets`.
+                return Vec::new();
+            };
+            let scene =
+                assets.load::<Scene>(GltfAssetLabel::Scene(0).from_asset(model.to_string()));
+            let animations = [0, 1, 2].map(|index| {
+                assets
+                    .load::<AnimationClip>(
+                        GltfAssetLabel::Animation(index).from_asset(model.to_string()),
+                    )
+                    .untyped()
+            });
+            std::iter::once(scene.untyped()).chain(animations).collect()
+        },
+    );
+}
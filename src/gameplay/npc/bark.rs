@@ -0,0 +1,322 @@
+//! Barks are short lines that NPCs say on their own, shown in a speech bubble above their head.
+//!
+//! An NPC takes its barks from the Yarn node in its `bark_node` property. Each line of that node is
+//! tagged with the situation it is said in, e.g. `#bark:proximity`. The node is never run as a dialogue.
+//! Lines that start with the speaker's name, e.g. `The Follower: Hi!`, need to be tagged `#character` so that
+//! only the text after the name is shown. Untagged lines are shown as written, even if they contain a colon.
+//! NPCs without a bark node, or without lines for a situation, fall back to the [`BarkTable`].
+//! The tagged lines are collected into [`YarnBarks`] once, when the Yarn project has been compiled.
+
+use avian_pickup::output::PropThrown;
+use bevy::{prelude::*, utils::HashMap};
+use bevy_yarnspinner::prelude::*;
+use rand::seq::SliceRandom as _;
+
+use crate::{
+    AppSet,
+    gameplay::player::{Player, camera::WorldModelCamera},
+    menus::{Menu, is_menu_open},
+    screens::Screen,
+    theme::palette::*,
+    third_party::bevy_yarnspinner::is_dialogue_running,
+};
+
+use super::Npc;
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<(NpcBarks, BarkBubble)>();
+    app.init_resource::<BarkTable>();
+    app.init_resource::<YarnBarks>();
+    app.add_systems(
+        Update,
+        collect_yarn_barks.run_if(resource_added::<YarnProject>),
+    );
+    app.add_systems(
+        Update,
+        (
+            tick_bark_cooldowns.in_set(AppSet::TickTimers),
+            (
+                bark_on_proximity,
+                bark_on_prop_thrown.run_if(on_event::<PropThrown>),
+            )
+                .run_if(not(is_dialogue_running.or(is_menu_open)))
+                .in_set(AppSet::Update),
+            despawn_bubbles
+                .run_if(is_dialogue_running)
+                .in_set(AppSet::Update),
+            follow_speaker_with_bubble.in_set(AppSet::ChangeUi),
+        )
+            .run_if(in_state(Screen::Gameplay)),
+    );
+    app.add_systems(OnEnter(Menu::Pause), despawn_bubbles);
+}
+
+/// A situation that makes NPCs bark.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum BarkTrigger {
+    /// The player came close.
+    Proximity,
+    /// The player threw a prop near the NPC.
+    PropThrown,
+}
+
+impl BarkTrigger {
+    const ALL: [Self; 2] = [Self::Proximity, Self::PropThrown];
+
+    fn tag(self) -> &'static str {
+        match self {
+            Self::Proximity => "bark:proximity",
+            Self::PropThrown => "bark:thrown",
+        }
+    }
+}
+
+/// The barks of NPCs that have no bark node of their own.
+#[derive(Resource, Debug)]
+struct BarkTable(HashMap<BarkTrigger, Vec<&'static str>>);
+
+impl Default for BarkTable {
+    fn default() -> Self {
+        Self(HashMap::from_iter([
+            (
+                BarkTrigger::Proximity,
+                vec!["Hello there.", "Nice day, isn't it?"],
+            ),
+            (BarkTrigger::PropThrown, vec!["Hey!", "Watch it!"]),
+        ]))
+    }
+}
+
+/// The barks found in the Yarn project, by bark node and trigger.
+#[derive(Resource, Debug, Default)]
+struct YarnBarks(HashMap<(String, BarkTrigger), Vec<String>>);
+
+fn collect_yarn_barks(mut yarn_barks: ResMut<YarnBarks>, yarn_project: Res<YarnProject>) {
+    yarn_barks.0.clear();
+    for line in yarn_project.compilation().string_table.values() {
+        for trigger in BarkTrigger::ALL {
+            if line.metadata.iter().any(|tag| tag == trigger.tag()) {
+                yarn_barks
+                    .0
+                    .entry((line.node_name.clone(), trigger))
+                    .or_default()
+                    .push(bark_text(&line.text, &line.metadata).to_string());
+            }
+        }
+    }
+}
+
+/// How soon an NPC may bark again.
+const BARK_COOLDOWN_SECONDS: f32 = 8.0;
+/// How long a bark stays on screen.
+const BARK_SECONDS: f32 = 3.0;
+/// NPCs bark when the player gets this close.
+const PROXIMITY_DISTANCE: f32 = 4.0;
+/// NPCs bark when the player throws a prop this close to them.
+const PROP_THROWN_DISTANCE: f32 = 8.0;
+/// How far above the NPC's origin the bubble floats.
+const BUBBLE_HEIGHT: f32 = 1.5;
+
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component, Default, Debug)]
+pub(super) struct NpcBarks {
+    cooldown: Timer,
+    player_was_near: bool,
+}
+
+impl Default for NpcBarks {
+    fn default() -> Self {
+        let mut cooldown = Timer::from_seconds(BARK_COOLDOWN_SECONDS, TimerMode::Once);
+        // Allow barking right away.
+        cooldown.tick(cooldown.duration());
+        Self {
+            cooldown,
+            player_was_near: false,
+        }
+    }
+}
+
+/// A speech bubble that follows the NPC that barked it.
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component, Debug)]
+struct BarkBubble {
+    speaker: Entity,
+    lifetime: Timer,
+}
+
+fn tick_bark_cooldowns(time: Res<Time>, mut q_barks: Query<&mut NpcBarks>) {
+    for mut barks in &mut q_barks {
+        barks.cooldown.tick(time.delta());
+    }
+}
+
+fn bark_on_proximity(
+    mut commands: Commands,
+    mut q_npc: Query<(Entity, &Npc, &GlobalTransform, &mut NpcBarks)>,
+    player: Single<&GlobalTransform, With<Player>>,
+    yarn_barks: Res<YarnBarks>,
+    bark_table: Res<BarkTable>,
+) {
+    for (entity, npc, transform, mut barks) in &mut q_npc {
+        let is_near = transform.translation().distance(player.translation()) < PROXIMITY_DISTANCE;
+        let came_near = is_near && !barks.player_was_near;
+        barks.player_was_near = is_near;
+        if came_near {
+            bark(
+                &mut commands,
+                entity,
+                npc,
+                &mut barks,
+                BarkTrigger::Proximity,
+                &yarn_barks,
+                &bark_table,
+            );
+        }
+    }
+}
+
+fn bark_on_prop_thrown(
+    mut commands: Commands,
+    mut prop_thrown: EventReader<PropThrown>,
+    q_prop: Query<&GlobalTransform>,
+    mut q_npc: Query<(Entity, &Npc, &GlobalTransform, &mut NpcBarks)>,
+    yarn_barks: Res<YarnBarks>,
+    bark_table: Res<BarkTable>,
+) {
+    for event in prop_thrown.read() {
+        let Ok(prop) = q_prop.get(event.prop) else {
+            continue;
+        };
+        for (entity, npc, transform, mut barks) in &mut q_npc {
+            if transform.translation().distance(prop.translation()) < PROP_THROWN_DISTANCE {
+                bark(
+                    &mut commands,
+                    entity,
+                    npc,
+                    &mut barks,
+                    BarkTrigger::PropThrown,
+                    &yarn_barks,
+                    &bark_table,
+                );
+            }
+        }
+    }
+}
+
+fn bark(
+    commands: &mut Commands,
+    speaker: Entity,
+    npc: &Npc,
+    barks: &mut NpcBarks,
+    trigger: BarkTrigger,
+    yarn_barks: &YarnBarks,
+    bark_table: &BarkTable,
+) {
+    if !barks.cooldown.finished() {
+        return;
+    }
+    let rng = &mut rand::thread_rng();
+    let from_node = yarn_barks
+        .0
+        .get(&(npc.bark_node.clone(), trigger))
+        .and_then(|lines| lines.choose(rng));
+    let text = match from_node {
+        Some(text) => text.clone(),
+        None => {
+            let Some(text) = bark_table
+                .0
+                .get(&trigger)
+                .and_then(|lines| lines.choose(rng))
+            else {
+                return;
+            };
+            text.to_string()
+        }
+    };
+    barks.cooldown.reset();
+
+    commands
+        .spawn((
+            Name::new("Bark Bubble"),
+            BarkBubble {
+                speaker,
+                lifetime: Timer::from_seconds(BARK_SECONDS, TimerMode::Once),
+            },
+            Node {
+                position_type: PositionType::Absolute,
+                padding: UiRect::axes(Val::Px(10.0), Val::Px(4.0)),
+                ..default()
+            },
+            BackgroundColor(OVERLAY_BACKGROUND),
+            // Hidden until it has been placed above the speaker.
+            Visibility::Hidden,
+            StateScoped(Screen::Gameplay),
+        ))
+        .with_child((
+            Text(text),
+            TextFont::from_font_size(20.0),
+            TextColor(BUTTON_TEXT),
+        ));
+}
+
+/// Tags a line that starts with the name of its speaker.
+const CHARACTER_TAG: &str = "character";
+
+/// The text of a bark line, without the speaker's name if the line is tagged with [`CHARACTER_TAG`].
+fn bark_text<'a>(text: &'a str, metadata: &[String]) -> &'a str {
+    let has_character_name = metadata.iter().any(|tag| tag == CHARACTER_TAG);
+    if !has_character_name {
+        return text;
+    }
+    text.split_once(':')
+        .map_or(text, |(_, text)| text.trim_start())
+}
+
+/// Barks would cover the dialogue and menus, so they are cut short.
+fn despawn_bubbles(mut commands: Commands, q_bubble: Query<Entity, With<BarkBubble>>) {
+    for entity in &q_bubble {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+fn follow_speaker_with_bubble(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut q_bubble: Query<(
+        Entity,
+        &mut BarkBubble,
+        &mut Node,
+        &mut Visibility,
+        &ComputedNode,
+    )>,
+    q_speaker: Query<&GlobalTransform>,
+    camera: Single<(&Camera, &GlobalTransform), With<WorldModelCamera>>,
+) {
+    let (camera, camera_transform) = camera.into_inner();
+    for (entity, mut bubble, mut node, mut visibility, computed) in &mut q_bubble {
+        bubble.lifetime.tick(time.delta());
+        let Ok(speaker) = q_speaker.get(bubble.speaker) else {
+            commands.entity(entity).despawn_recursive();
+            continue;
+        };
+        if bubble.lifetime.finished() {
+            commands.entity(entity).despawn_recursive();
+            continue;
+        }
+        let anchor = speaker.translation() + Vec3::Y * BUBBLE_HEIGHT;
+        let size = computed.size() * computed.inverse_scale_factor();
+        let Ok(position) = camera.world_to_viewport(camera_transform, anchor) else {
+            // The speaker is behind the camera.
+            *visibility = Visibility::Hidden;
+            continue;
+        };
+        if size == Vec2::ZERO {
+            // The bubble has not been laid out yet.
+            continue;
+        }
+        // Center the bubble horizontally above the anchor.
+        node.left = Val::Px(position.x - size.x / 2.0);
+        node.top = Val::Px(position.y - size.y);
+        *visibility = Visibility::Inherited;
+    }
+}
@@ -0,0 +1,224 @@
+//! A state machine that decides what an NPC is doing.
+//! The [`ai`](super::ai) module then turns the current [`NpcBehaviour`] into movement.
+
+use avian_pickup::prop::HeldProp;
+use avian3d::prelude::*;
+use bevy::prelude::*;
+use bevy_trenchbroom::prelude::*;
+
+use crate::{
+    AppSet,
+    gameplay::player::{Player, pickup::is_holding_prop},
+    screens::Screen,
+    third_party::{avian3d::CollisionLayer, bevy_yarnspinner::InDialogue},
+};
+
+use super::{Npc, patrol::Patrol};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<(NpcBehaviour, NpcBrain)>();
+    app.add_systems(
+        Update,
+        update_behaviour
+            .param_warn_once()
+            .run_if(in_state(Screen::Gameplay))
+            .in_set(AppSet::Update),
+    );
+}
+
+#[derive(FgdType, Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+#[reflect(Debug, PartialEq, Default)]
+pub(crate) enum NpcBehaviour {
+    /// Stand around.
+    #[default]
+    Idle,
+    /// Walk along the `path_corner`s the NPC targets.
+    Patrol,
+    /// Walk to the player whenever they are in sight, and patrol otherwise.
+    Follow,
+    /// Run away from a player that comes too close.
+    /// Every other NPC only flees from a player that is threatening it with a prop.
+    Flee,
+    /// Stand still and face the player, e.g. while talking to them.
+    Converse,
+}
+
+#[derive(Debug, Component, Reflect)]
+#[reflect(Component, Default, Debug)]
+pub(crate) struct NpcBrain {
+    /// What the NPC is currently doing.
+    pub(crate) behaviour: NpcBehaviour,
+    /// What the NPC does when nothing in particular is happening, as configured in TrenchBroom.
+    fallback: NpcBehaviour,
+    /// Whether the NPC follows the player once it sees them.
+    pub(crate) follows_player: bool,
+    /// Seconds since the NPC last saw the player.
+    since_player_seen: f32,
+}
+
+impl Default for NpcBrain {
+    fn default() -> Self {
+        Self {
+            behaviour: default(),
+            fallback: default(),
+            follows_player: true,
+            since_player_seen: f32::INFINITY,
+        }
+    }
+}
+
+impl NpcBrain {
+    /// A brain that falls back to `behaviour` when nothing in particular is happening.
+    pub(crate) fn new(behaviour: NpcBehaviour) -> Self {
+        Self {
+            fallback: behaviour,
+            follows_player: behaviour == NpcBehaviour::Follow,
+            ..default()
+        }
+    }
+
+    /// What the NPC does while it is neither talking, fleeing nor following the player.
+    fn resting_behaviour(&self, patrol: &Patrol, sees_player: bool) -> NpcBehaviour {
+        match self.fallback {
+            NpcBehaviour::Patrol | NpcBehaviour::Follow | NpcBehaviour::Flee
+                if patrol.corner.is_some() =>
+            {
+                NpcBehaviour::Patrol
+            }
+            NpcBehaviour::Converse if sees_player => NpcBehaviour::Converse,
+            _ => NpcBehaviour::Idle,
+        }
+    }
+}
+
+/// How far NPCs can see.
+const SIGHT_RANGE: f32 = 20.0;
+/// How long NPCs keep following the player after losing sight of them.
+const FOLLOW_MEMORY_SECONDS: f32 = 3.0;
+/// How close a player holding a prop can get before the NPC flees.
+pub(super) const FLEE_DISTANCE: f32 = 4.0;
+/// Fleeing NPCs stop once they are this far away from the player.
+const SAFE_DISTANCE: f32 = 2.0 * FLEE_DISTANCE;
+/// Height above the NPC's and player's origin from which line of sight is checked.
+const EYE_HEIGHT: f32 = 0.5;
+
+fn update_behaviour(
+    time: Res<Time>,
+    mut q_npc: Query<
+        (
+            Entity,
+            &GlobalTransform,
+            Has<InDialogue>,
+            &Patrol,
+            &mut NpcBrain,
+        ),
+        With<Npc>,
+    >,
+    player: Single<&GlobalTransform, With<Player>>,
+    q_held_prop: Query<&HeldProp>,
+    spatial_query: SpatialQuery,
+) {
+    let player_eyes = player.translation() + Vec3::Y * EYE_HEIGHT;
+    let is_threatening = is_holding_prop(q_held_prop);
+
+    for (entity, transform, in_dialogue, patrol, mut brain) in &mut q_npc {
+        let eyes = transform.translation() + Vec3::Y * EYE_HEIGHT;
+        let distance = eyes.distance(player_eyes);
+        let sees_player =
+            distance < SIGHT_RANGE && has_line_of_sight(entity, eyes, player_eyes, &spatial_query);
+        if sees_player {
+            brain.since_player_seen = 0.0;
+        } else {
+            brain.since_player_seen += time.delta_secs();
+        }
+
+        let is_scared = is_threatening || brain.fallback == NpcBehaviour::Flee;
+        let behaviour = if in_dialogue {
+            NpcBehaviour::Converse
+        } else if (is_scared && sees_player && distance < FLEE_DISTANCE)
+            || (brain.behaviour == NpcBehaviour::Flee && distance < SAFE_DISTANCE)
+        {
+            NpcBehaviour::Flee
+        } else if brain.follows_player && brain.since_player_seen < FOLLOW_MEMORY_SECONDS {
+            NpcBehaviour::Follow
+        } else {
+            brain.resting_behaviour(patrol, sees_player)
+        };
+        if brain.behaviour != behaviour {
+            brain.behaviour = behaviour;
+        }
+    }
+}
+
+/// Checks whether the level geometry blocks the view between two points.
+fn has_line_of_sight(npc: Entity, from: Vec3, to: Vec3, spatial_query: &SpatialQuery) -> bool {
+    let Ok((direction, distance)) = Dir3::new_and_length(to - from) else {
+        return true;
+    };
+    let filter =
+        SpatialQueryFilter::from_mask(CollisionLayer::Default).with_excluded_entities([npc]);
+    spatial_query
+        .cast_ray(from, direction, distance, true, &filter)
+        .is_none()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f32::consts::TAU;
+
+    use bevy::state::app::StatesPlugin;
+
+    use super::*;
+
+    /// About as many as in the `stress_npcs` map.
+    const NPC_COUNT: usize = 100;
+
+    #[test]
+    fn npcs_follow_a_visible_player_and_idle_otherwise() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, StatesPlugin, AssetPlugin::default()));
+        // NPCs start loading their model when they are spawned.
+        app.init_asset::<Scene>();
+        // Without any colliders, nothing blocks the line of sight.
+        app.init_resource::<SpatialQueryPipeline>();
+        app.insert_state(Screen::Gameplay);
+        app.add_plugins(plugin);
+
+        app.world_mut().spawn((Player, GlobalTransform::IDENTITY));
+        let npcs: Vec<_> = (0..NPC_COUNT)
+            .map(|index| {
+                // Every other NPC stands too far away to see the player.
+                let distance = if index % 2 == 0 {
+                    SIGHT_RANGE / 2.0
+                } else {
+                    SIGHT_RANGE * 2.0
+                };
+                let angle = index as f32 / NPC_COUNT as f32 * TAU;
+                let translation = Quat::from_rotation_y(angle) * Vec3::X * distance;
+                app.world_mut()
+                    .spawn((
+                        Npc {
+                            behaviour: NpcBehaviour::Follow,
+                            ..default()
+                        },
+                        GlobalTransform::from_translation(translation),
+                    ))
+                    .id()
+            })
+            .collect();
+
+        // Make sure the brains inserted when spawning the NPCs have been ticked.
+        app.update();
+        app.update();
+
+        for (index, npc) in npcs.into_iter().enumerate() {
+            let expected = if index % 2 == 0 {
+                NpcBehaviour::Follow
+            } else {
+                NpcBehaviour::Idle
+            };
+            let brain = app.world().get::<NpcBrain>(npc).unwrap();
+            assert_eq!(brain.behaviour, expected, "NPC {index}");
+        }
+    }
+}
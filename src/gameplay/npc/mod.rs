@@ -1,68 +1,120 @@
 use std::f32::consts::PI;
 
 use animation::{NpcAnimationState, setup_npc_animations};
-use assets::NpcAssets;
 use avian3d::prelude::*;
+use bark::NpcBarks;
+use behaviour::{NpcBehaviour, NpcBrain};
 use bevy::{
     ecs::{component::ComponentId, world::DeferredWorld},
     prelude::*,
 };
 use bevy_tnua::{TnuaAnimatingState, prelude::*};
 use bevy_tnua_avian3d::TnuaAvian3dSensorShape;
-use bevy_trenchbroom::prelude::*;
+use bevy_trenchbroom::{class::QuakeClass, prelude::*};
+use patrol::Patrol;
 
-use crate::third_party::{bevy_trenchbroom::fix_gltf_rotation, bevy_yarnspinner::YarnNode};
+use crate::third_party::{
+    bevy_trenchbroom::{fix_gltf_rotation, target::Target},
+    bevy_yarnspinner::YarnNode,
+};
 
-use super::animation::AnimationPlayerAncestor;
+use super::{animation::AnimationPlayerAncestor, footstep::Strides, surface::GroundSurface};
 mod ai;
 mod animation;
 mod assets;
+mod bark;
+pub(crate) mod behaviour;
+pub(crate) mod patrol;
 mod sound;
 
 pub(super) fn plugin(app: &mut App) {
-    app.add_plugins((ai::plugin, animation::plugin, assets::plugin, sound::plugin));
+    app.add_plugins((
+        ai::plugin,
+        animation::plugin,
+        assets::plugin,
+        bark::plugin,
+        behaviour::plugin,
+        patrol::plugin,
+        sound::plugin,
+    ));
     app.register_type::<Npc>();
 }
 
-#[derive(PointClass, Component, Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
-#[reflect(Component)]
-#[base(Transform, Visibility)]
+#[derive(PointClass, Component, Debug, Clone, PartialEq, Reflect)]
+#[reflect(Component, Default, Debug)]
+#[base(Transform, Visibility, Target, YarnNode)]
 #[model("models/fox/Fox.gltf")]
 #[component(on_add = Self::on_add)]
 // In Wasm, TrenchBroom classes are not automatically registered.
 // So, we need to manually register the class in `src/third_party/bevy_trenchbroom/mod.rs`.
-pub(crate) struct Npc;
+pub(crate) struct Npc {
+    /// The speed the NPC prefers to walk at.
+    pub(crate) desired_speed: f32,
+    /// The speed the NPC is allowed to reach to avoid obstacles.
+    pub(crate) max_speed: f32,
+    pub(crate) radius: f32,
+    /// How high above the ground the NPC's origin floats.
+    pub(crate) float_height: f32,
+    /// The glTF file of the NPC's model. Its first three animations must be run, idle and walk.
+    pub(crate) model: String,
+    /// What the NPC does when nothing in particular is happening.
+    pub(crate) behaviour: NpcBehaviour,
+    /// The Yarn node with the NPC's barks, see the `bark` module.
+    pub(crate) bark_node: String,
+}
+
+impl Default for Npc {
+    fn default() -> Self {
+        Self {
+            desired_speed: 7.0,
+            max_speed: 8.0,
+            radius: 0.8,
+            float_height: 1.0,
+            model: Npc::CLASS_INFO.model_path().unwrap().to_string(),
+            behaviour: NpcBehaviour::Follow,
+            bark_node: String::new(),
+        }
+    }
+}
 
-const NPC_RADIUS: f32 = 0.8;
-const NPC_FLOAT_HEIGHT: f32 = 1.0;
+/// NPCs are smaller than the player, so they take shorter strides.
+const NPC_STRIDE_LENGTH: f32 = 1.3;
 
 impl Npc {
     fn on_add(mut world: DeferredWorld, entity: Entity, _id: ComponentId) {
         if world.is_scene_world() {
             return;
         }
-        let model = world.resource::<NpcAssets>().model.clone();
+        let npc = world.get::<Npc>(entity).unwrap().clone();
+        let model = world
+            .resource::<AssetServer>()
+            .load(GltfAssetLabel::Scene(0).from_asset(npc.model));
         world
             .commands()
             .entity(entity)
             .queue(fix_gltf_rotation)
             .insert((
-                Npc,
                 TransformInterpolation,
-                Collider::capsule(NPC_RADIUS, 0.1),
+                Collider::capsule(npc.radius, 0.1),
                 TnuaController::default(),
-                TnuaAvian3dSensorShape(Collider::cylinder(NPC_RADIUS - 0.01, 0.0)),
+                TnuaAvian3dSensorShape(Collider::cylinder(npc.radius - 0.01, 0.0)),
                 ColliderDensity(200.0),
                 RigidBody::Dynamic,
                 LockedAxes::ROTATION_LOCKED.unlock_rotation_y(),
                 TnuaAnimatingState::<NpcAnimationState>::default(),
                 AnimationPlayerAncestor,
-                YarnNode::new("Npc"),
+                (
+                    NpcBrain::new(npc.behaviour),
+                    Patrol::default(),
+                    Strides::new(NPC_STRIDE_LENGTH),
+                    NpcBarks::default(),
+                    GroundSurface::default(),
+                ),
             ))
             .with_child((
                 Name::new("Npc Model"),
                 SceneRoot(model),
-                Transform::from_xyz(0.0, -NPC_FLOAT_HEIGHT, 0.0)
+                Transform::from_xyz(0.0, -npc.float_height, 0.0)
                     .with_rotation(Quat::from_rotation_y(PI)),
             ))
             .observe(setup_npc_animations);
@@ -0,0 +1,98 @@
+//! NPCs patrol along a chain of `path_corner` entities, starting at the one their `target` points to.
+
+use bevy::prelude::*;
+use bevy_trenchbroom::prelude::*;
+
+use crate::{
+    AppSet,
+    screens::Screen,
+    third_party::bevy_trenchbroom::target::{Target, TargetName},
+};
+
+use super::{
+    Npc,
+    behaviour::{NpcBehaviour, NpcBrain},
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<(PathCorner, Patrol)>();
+    app.add_systems(
+        Update,
+        advance_patrol
+            .run_if(in_state(Screen::Gameplay))
+            .in_set(AppSet::Update),
+    );
+}
+
+/// A waypoint of a patrol route. Its `target` is the next waypoint.
+#[derive(PointClass, Component, Debug, Clone, Default, Reflect)]
+#[reflect(Component, Default, Debug)]
+#[base(Transform, TargetName, Target)]
+// In Wasm, TrenchBroom classes are not automatically registered.
+// So, we need to manually register the class in `src/third_party/bevy_trenchbroom/mod.rs`.
+pub(crate) struct PathCorner {
+    /// Seconds to wait at this corner before moving on.
+    pub(crate) wait: f32,
+}
+
+/// The progress of an NPC along its patrol route.
+#[derive(Component, Debug, Default, Reflect)]
+#[reflect(Component, Default, Debug)]
+pub(crate) struct Patrol {
+    /// The corner the NPC is currently walking to or waiting at.
+    pub(crate) corner: Option<Entity>,
+    /// Time left to wait at the current corner.
+    wait: Option<Timer>,
+}
+
+impl Patrol {
+    /// Whether the NPC should stand still instead of walking to the current corner.
+    pub(crate) fn is_waiting(&self) -> bool {
+        self.wait.is_some()
+    }
+}
+
+/// How close an NPC needs to get to a corner to count as having reached it.
+/// Must be larger than the distance at which the navigation agent considers its target reached.
+const CORNER_REACHED_DISTANCE: f32 = 2.5;
+
+fn advance_patrol(
+    time: Res<Time>,
+    mut q_npc: Query<(&GlobalTransform, &Target, &NpcBrain, &mut Patrol), With<Npc>>,
+    q_corner: Query<(Entity, &TargetName, &Target, &PathCorner, &GlobalTransform)>,
+) {
+    for (transform, npc_target, brain, mut patrol) in &mut q_npc {
+        let corners = q_corner.iter().map(|(entity, name, ..)| (entity, name));
+        let Some(corner) = patrol.corner.or_else(|| npc_target.find(corners)) else {
+            continue;
+        };
+        if patrol.corner != Some(corner) {
+            patrol.corner = Some(corner);
+        }
+        if brain.behaviour != NpcBehaviour::Patrol {
+            continue;
+        }
+        let Ok((_, _, corner_target, path_corner, corner_transform)) = q_corner.get(corner) else {
+            patrol.corner = None;
+            continue;
+        };
+
+        if let Some(wait) = patrol.wait.as_mut() {
+            wait.tick(time.delta());
+            if wait.finished() {
+                patrol.wait = None;
+                let corners = q_corner.iter().map(|(entity, name, ..)| (entity, name));
+                patrol.corner = corner_target.find(corners).or(Some(corner));
+            }
+            continue;
+        }
+
+        let distance = transform
+            .translation()
+            .xz()
+            .distance(corner_transform.translation().xz());
+        if distance < CORNER_REACHED_DISTANCE {
+            patrol.wait = Some(Timer::from_seconds(path_corner.wait, TimerMode::Once));
+        }
+    }
+}
@@ -1,62 +1,41 @@
-use std::time::Duration;
-
-use avian3d::prelude::LinearVelocity;
 use bevy::{
     audio::{SpatialScale, Volume},
     prelude::*,
 };
-use bevy_tnua::prelude::*;
 
-use crate::{AppSet, audio::SoundEffect, screens::Screen};
+use crate::{
+    audio::SoundEffect,
+    gameplay::{footstep::Footstep, material_sound::MaterialSoundAssets, surface::GroundSurface},
+};
 
-use super::{Npc, assets::NpcAssets};
+use super::Npc;
 
 pub(super) fn plugin(app: &mut App) {
-    app.add_systems(
-        Update,
-        play_step_sound
-            .run_if(in_state(Screen::Gameplay))
-            .in_set(AppSet::PlaySounds),
-    );
+    app.add_observer(play_step_sound);
 }
 
+/// Plays the step sounds of the surface the NPC walks on, just like for the player.
+/// Like all material sounds, these are rock recordings for now, played at the material's speed.
 fn play_step_sound(
+    trigger: Trigger<Footstep>,
     mut commands: Commands,
-    npc: Single<(Entity, &TnuaController, &LinearVelocity), With<Npc>>,
-    mut npc_assets: ResMut<NpcAssets>,
-    time: Res<Time>,
-    mut timer: Local<Option<Timer>>,
+    q_npc: Query<&GroundSurface, With<Npc>>,
+    mut material_sounds: ResMut<MaterialSoundAssets>,
 ) {
-    let base_millis = 300;
-    let timer = timer.get_or_insert_with(|| {
-        Timer::new(Duration::from_millis(base_millis), TimerMode::Repeating)
-    });
-    timer.tick(time.delta());
-    if !timer.finished() {
-        return;
-    }
-
-    let (entity, controller, linear_velocity) = npc.into_inner();
-    if controller.is_airborne().unwrap_or(true) {
-        return;
-    }
-    let speed = linear_velocity.length();
-    if speed < 1.0 {
+    let entity = trigger.entity();
+    let Ok(surface) = q_npc.get(entity) else {
         return;
-    }
-    // at speed = 5 m/s, halve the duration
-    let speed_to_half_duration = 5.0;
-    let factor = 1.0 - (speed - speed_to_half_duration) / speed_to_half_duration;
-    timer.set_duration(Duration::from_millis((base_millis as f32 * factor) as u64));
+    };
     let rng = &mut rand::thread_rng();
-    let sound_effect = npc_assets.steps.pick(rng).clone();
+    let surface_sounds = surface.0.sounds(&mut material_sounds);
+    let sound_effect = surface_sounds.run_steps.pick(rng).clone();
 
     commands.entity(entity).with_child((
         Transform::default(),
-        AudioPlayer(sound_effect.clone()),
+        AudioPlayer(sound_effect),
         PlaybackSettings::DESPAWN
             .with_spatial(true)
-            .with_speed(1.5)
+            .with_speed(surface_sounds.speed)
             .with_volume(Volume::new(1.6))
             .with_spatial_scale(SpatialScale::new(0.02)),
         SoundEffect,
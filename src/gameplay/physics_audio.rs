@@ -0,0 +1,135 @@
+//! Props make a sound when they hit something, e.g. a thrown crate landing on the floor.
+//! Each prop sounds like its own [`PhysicsMaterial`], and the level geometry it hits sounds like its surface.
+//! Both get louder the harder the impact.
+//! Note that all materials currently share the same recordings at different pitches, see [`crate::gameplay::material_sound`].
+
+use std::time::Duration;
+
+use avian3d::prelude::*;
+use bevy::{audio::Volume, prelude::*};
+
+use crate::{
+    AppSet,
+    audio::SoundEffect,
+    screens::Screen,
+    third_party::avian3d::{CollisionLayer, PhysicsMaterial},
+};
+
+use super::{material_sound::MaterialSoundAssets, surface::Surfaces};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<ImpactSoundCooldown>();
+    app.register_required_components::<PhysicsMaterial, ImpactSoundCooldown>();
+    app.add_systems(
+        Update,
+        (
+            tick_cooldowns.in_set(AppSet::TickTimers),
+            play_impact_sounds
+                .run_if(on_event::<Collision>)
+                .in_set(AppSet::PlaySounds),
+        )
+            .run_if(in_state(Screen::Gameplay)),
+    );
+}
+
+/// Impacts that change a body's speed by less than this (in m/s) are silent.
+/// Bodies resting on the floor are pushed up by gravity every frame, which should not make a sound.
+const MIN_SPEED_CHANGE: f32 = 1.0;
+/// Impacts that change a body's speed by this much (in m/s) are played at full volume.
+const FULL_VOLUME_SPEED_CHANGE: f32 = 8.0;
+/// The minimum time between two impact sounds of the same body,
+/// so that a prop sliding or bouncing along the floor doesn't play a sound every frame.
+const COOLDOWN: Duration = Duration::from_millis(150);
+
+#[derive(Debug, Component, Reflect, Clone)]
+#[reflect(Component)]
+struct ImpactSoundCooldown(Timer);
+
+impl Default for ImpactSoundCooldown {
+    fn default() -> Self {
+        let mut timer = Timer::new(COOLDOWN, TimerMode::Once);
+        // A freshly spawned body should be able to make a sound right away.
+        timer.tick(COOLDOWN);
+        Self(timer)
+    }
+}
+
+fn tick_cooldowns(mut q_cooldown: Query<&mut ImpactSoundCooldown>, time: Res<Time>) {
+    for mut cooldown in &mut q_cooldown {
+        cooldown.0.tick(time.delta());
+    }
+}
+
+fn play_impact_sounds(
+    mut commands: Commands,
+    mut collisions: EventReader<Collision>,
+    q_collider: Query<&CollisionLayers>,
+    mut q_body: Query<(
+        &PhysicsMaterial,
+        &mut ImpactSoundCooldown,
+        &ComputedMass,
+        &GlobalTransform,
+    )>,
+    mut surfaces: Surfaces,
+    mut material_sounds: ResMut<MaterialSoundAssets>,
+) {
+    let rng = &mut rand::thread_rng();
+    for Collision(contacts) in collisions.read() {
+        let is_prop = |collider: Entity| {
+            q_collider
+                .get(collider)
+                .is_ok_and(|layers| layers.memberships.has_all(CollisionLayer::Prop))
+        };
+        let sides = [
+            (contacts.entity1, contacts.body_entity1, contacts.entity2),
+            (contacts.entity2, contacts.body_entity2, contacts.entity1),
+        ];
+        for (collider, body, other) in sides {
+            if !is_prop(collider) {
+                continue;
+            }
+            let Some(body) = body else {
+                continue;
+            };
+            let Ok((material, mut cooldown, mass, transform)) = q_body.get_mut(body) else {
+                continue;
+            };
+            if !cooldown.0.finished() {
+                continue;
+            }
+            let speed_change = contacts.total_normal_impulse * mass.inverse();
+            if speed_change < MIN_SPEED_CHANGE {
+                continue;
+            }
+            cooldown.0.reset();
+
+            let volume = ((speed_change - MIN_SPEED_CHANGE)
+                / (FULL_VOLUME_SPEED_CHANGE - MIN_SPEED_CHANGE))
+                .clamp(0.1, 1.0);
+            // Other props make their own sound, but the level can't,
+            // so the prop also plays the sound of the surface it hit.
+            // Most impacts are props landing on the floor, so we look at the face below the prop.
+            let surface = if is_prop(other) {
+                None
+            } else {
+                let ray = Ray3d::new(transform.translation(), Dir3::NEG_Y);
+                surfaces
+                    .material_of(other, ray)
+                    .filter(|surface| surface != material)
+            };
+            for material in std::iter::once(*material).chain(surface) {
+                let sounds = material.sounds(&mut material_sounds);
+                commands.spawn((
+                    Name::new("Impact Sound"),
+                    Transform::from_translation(transform.translation()),
+                    AudioPlayer(sounds.impacts.pick(rng).clone()),
+                    PlaybackSettings::DESPAWN
+                        .with_spatial(true)
+                        .with_volume(Volume::new(volume))
+                        .with_speed(sounds.speed),
+                    SoundEffect,
+                ));
+            }
+        }
+    }
+}
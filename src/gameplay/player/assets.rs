@@ -20,14 +20,8 @@ pub(crate) struct PlayerAssets {
     #[dependency]
     pub(crate) throw_sound: Handle<AudioSource>,
     #[dependency]
-    pub(crate) steps: ShuffleBag<Handle<AudioSource>>,
-    #[dependency]
     pub(crate) jump_grunts: ShuffleBag<Handle<AudioSource>>,
     #[dependency]
-    pub(crate) land_sounds: ShuffleBag<Handle<AudioSource>>,
-    #[dependency]
-    pub(crate) jump_start_sounds: ShuffleBag<Handle<AudioSource>>,
-    #[dependency]
     pub(crate) idle_animation: Handle<AnimationClip>,
     #[dependency]
     pub(crate) a_pose_animation: Handle<AnimationClip>,
@@ -40,21 +34,6 @@ impl FromWorld for PlayerAssets {
         Self {
             model: assets.load(Player::scene_path()),
             throw_sound: assets.load("audio/sound_effects/throw.ogg"),
-            steps: ShuffleBag::try_new(
-                [
-                    assets.load("audio/sound_effects/step/Footsteps_Rock_Walk_01.ogg"),
-                    assets.load("audio/sound_effects/step/Footsteps_Rock_Walk_02.ogg"),
-                    assets.load("audio/sound_effects/step/Footsteps_Rock_Walk_03.ogg"),
-                    assets.load("audio/sound_effects/step/Footsteps_Rock_Walk_04.ogg"),
-                    assets.load("audio/sound_effects/step/Footsteps_Rock_Walk_05.ogg"),
-                    assets.load("audio/sound_effects/step/Footsteps_Rock_Walk_06.ogg"),
-                    assets.load("audio/sound_effects/step/Footsteps_Rock_Walk_07.ogg"),
-                    assets.load("audio/sound_effects/step/Footsteps_Rock_Walk_08.ogg"),
-                    assets.load("audio/sound_effects/step/Footsteps_Rock_Walk_09.ogg"),
-                ],
-                &mut rng,
-            )
-            .unwrap(),
             jump_grunts: ShuffleBag::try_new(
                 [
                     assets.load("audio/sound_effects/jump_grunt/jump_grunt_1.ogg"),
@@ -65,30 +44,6 @@ impl FromWorld for PlayerAssets {
                 &mut rng,
             )
             .unwrap(),
-            land_sounds: ShuffleBag::try_new(
-                [
-                    assets.load("audio/sound_effects/land/Footsteps_Rock_Jump_Land_01.ogg"),
-                    assets.load("audio/sound_effects/land/Footsteps_Rock_Jump_Land_02.ogg"),
-                    assets.load("audio/sound_effects/land/Footsteps_Rock_Jump_Land_03.ogg"),
-                    assets.load("audio/sound_effects/land/Footsteps_Rock_Jump_Land_04.ogg"),
-                    assets.load("audio/sound_effects/land/Footsteps_Rock_Jump_Land_05.ogg"),
-                    assets.load("audio/sound_effects/land/Footsteps_Rock_Jump_Land_06.ogg"),
-                ],
-                &mut rng,
-            )
-            .unwrap(),
-            jump_start_sounds: ShuffleBag::try_new(
-                [
-                    assets.load("audio/sound_effects/jump_start/Footsteps_Rock_Jump_Start_01.ogg"),
-                    assets.load("audio/sound_effects/jump_start/Footsteps_Rock_Jump_Start_02.ogg"),
-                    assets.load("audio/sound_effects/jump_start/Footsteps_Rock_Jump_Start_03.ogg"),
-                    assets.load("audio/sound_effects/jump_start/Footsteps_Rock_Jump_Start_04.ogg"),
-                    assets.load("audio/sound_effects/jump_start/Footsteps_Rock_Jump_Start_05.ogg"),
-                    assets.load("audio/sound_effects/jump_start/Footsteps_Rock_Jump_Start_06.ogg"),
-                ],
-                &mut rng,
-            )
-            .unwrap(),
             idle_animation: assets.load(Player::animation_path(9)),
             a_pose_animation: assets.load(Player::animation_path(5)),
         }
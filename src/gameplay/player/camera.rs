@@ -6,6 +6,7 @@ use avian_pickup::prelude::*;
 use avian3d::prelude::*;
 use bevy::{
     core_pipeline::tonemapping::Tonemapping,
+    math::StableInterpolate as _,
     pbr::NotShadowCaster,
     prelude::*,
     render::{
@@ -15,15 +16,23 @@ use bevy::{
     scene::SceneInstanceReady,
 };
 use bevy_enhanced_input::prelude::*;
+use bevy_yarnspinner::events::DialogueCompleteEvent;
 
 use crate::{
     AppSet, CameraOrder, RenderLayer,
     gameplay::animation::{AnimationPlayerAncestor, AnimationPlayerLink},
     screens::Screen,
+    settings::Settings,
     third_party::avian3d::CollisionLayer,
 };
 
-use super::{PLAYER_FLOAT_HEIGHT, Player, assets::PlayerAssets, default_input::Rotate};
+use super::{
+    PLAYER_FLOAT_HEIGHT, Player,
+    assets::PlayerAssets,
+    camera_mode::CameraMode,
+    crouch::{CROUCHED_FLOAT_HEIGHT, CrouchState},
+    default_input::Rotate,
+};
 
 pub(super) fn plugin(app: &mut App) {
     app.add_observer(spawn_view_model);
@@ -31,14 +40,27 @@ pub(super) fn plugin(app: &mut App) {
     app.add_observer(rotate_camera_yaw_and_pitch.param_warn_once());
     app.add_systems(
         Update,
-        sync_camera_translation_with_player
-            .param_warn_once()
+        (
+            sync_camera_translation_with_player.param_warn_once(),
+            look_at_camera_target
+                .param_warn_once()
+                .run_if(resource_exists::<CameraTarget>),
+            forget_camera_target.run_if(on_event::<DialogueCompleteEvent>),
+        )
             .run_if(in_state(Screen::Gameplay))
             .in_set(AppSet::Update),
     );
+    app.add_systems(
+        Update,
+        (apply_fov_setting, apply_sensitivity_setting)
+            .run_if(resource_changed::<Settings>)
+            .in_set(AppSet::Update),
+    );
     app.register_type::<PlayerCameraParent>();
     app.register_type::<WorldModelCamera>();
+    app.register_type::<ViewModelCamera>();
     app.register_type::<CameraSensitivity>();
+    app.register_type::<CameraTarget>();
 }
 
 #[derive(Debug, Component, Reflect)]
@@ -46,23 +68,38 @@ pub(super) fn plugin(app: &mut App) {
 #[require(Transform, Visibility)]
 pub(crate) struct PlayerCameraParent;
 
+/// An entity the camera turns towards, e.g. to show the player something during a dialogue.
+/// Removed when the dialogue ends.
+#[derive(Debug, Resource, Reflect)]
+#[reflect(Resource)]
+pub(crate) struct CameraTarget(pub(crate) Entity);
+
 #[derive(Debug, Component, Reflect)]
 #[reflect(Component)]
-struct WorldModelCamera;
+pub(crate) struct WorldModelCamera;
+
+#[derive(Debug, Component, Reflect)]
+#[reflect(Component)]
+pub(crate) struct ViewModelCamera;
 
 #[derive(Debug, Component, Reflect, Deref, DerefMut)]
 #[reflect(Component)]
 pub(crate) struct CameraSensitivity(Vec2);
 
+impl CameraSensitivity {
+    fn from_settings(settings: &Settings) -> Self {
+        Self(Self::default().0 * settings.mouse_sensitivity)
+    }
+}
+
 impl Default for CameraSensitivity {
     fn default() -> Self {
         Self(
             // These factors are just arbitrary mouse sensitivity values.
-            // It's often nicer to have a faster horizontal sensitivity than vertical.
             // We use a component for them so that we can make them user-configurable at runtime
             // for accessibility reasons.
             // It also allows you to inspect them in an editor if you `Reflect` the component.
-            Vec2::new(0.003, 0.002),
+            Vec2::splat(0.002),
         )
     }
 }
@@ -71,6 +108,7 @@ fn spawn_view_model(
     _trigger: Trigger<OnAdd, Player>,
     mut commands: Commands,
     assets: Res<PlayerAssets>,
+    settings: Res<Settings>,
 ) {
     commands.insert_resource(AmbientLight {
         color: Color::srgb(1.0, 0.7, 0.4),
@@ -80,7 +118,8 @@ fn spawn_view_model(
         .spawn((
             Name::new("PlayerCameraParent"),
             PlayerCameraParent,
-            CameraSensitivity::default(),
+            CameraSensitivity::from_settings(&settings),
+            CameraMode::default(),
             StateScoped(Screen::Gameplay),
             AvianPickupActor {
                 prop_filter: SpatialQueryFilter::from_mask(CollisionLayer::Prop),
@@ -114,7 +153,7 @@ fn spawn_view_model(
                     ..default()
                 },
                 Projection::from(PerspectiveProjection {
-                    fov: 90.0_f32.to_radians(),
+                    fov: settings.fov.to_radians(),
                     ..default()
                 }),
                 RenderLayers::from(
@@ -127,6 +166,7 @@ fn spawn_view_model(
             // Spawn view model camera.
             parent.spawn((
                 Name::new("ViewModelCamera"),
+                ViewModelCamera,
                 Camera3d::default(),
                 Camera {
                     // Bump the order to render on top of the world model.
@@ -191,9 +231,10 @@ fn configure_player_view_model(
 
 fn rotate_camera_yaw_and_pitch(
     trigger: Trigger<Fired<Rotate>>,
-    mut transform: Single<&mut Transform, With<PlayerCameraParent>>,
+    camera_parent: Single<(&mut Transform, &CameraSensitivity), With<PlayerCameraParent>>,
 ) {
-    let delta = trigger.value;
+    let (mut transform, sensitivity) = camera_parent.into_inner();
+    let delta = trigger.value * sensitivity.0;
 
     if delta != Vec2::ZERO {
         // Note that we are not multiplying by delta_time here.
@@ -221,13 +262,49 @@ fn rotate_camera_yaw_and_pitch(
     }
 }
 
-fn sync_camera_translation_with_player(
+pub(super) fn sync_camera_translation_with_player(
     mut player_camera_parent: Single<&mut Transform, With<PlayerCameraParent>>,
-    player: Single<&Transform, (With<Player>, Without<PlayerCameraParent>)>,
+    player: Single<(&Transform, &CrouchState), (With<Player>, Without<PlayerCameraParent>)>,
+    time: Res<Time>,
+    mut height_offset: Local<Option<f32>>,
+) {
+    const STANDING_CAMERA_HEIGHT: f32 = 1.84;
+    const CROUCHED_CAMERA_HEIGHT: f32 = 1.1;
+    // How quickly the camera moves to its new height, see `StableInterpolate::smooth_nudge`.
+    const DECAY_RATE: f32 = 10.0;
+
+    let (player_transform, crouch) = player.into_inner();
+    // The player's body itself is lowered by Tnua when crouching, so we only need to adjust
+    // the camera's offset relative to the body.
+    let target_offset = if crouch.is_crouched() {
+        CROUCHED_CAMERA_HEIGHT - CROUCHED_FLOAT_HEIGHT
+    } else {
+        STANDING_CAMERA_HEIGHT - PLAYER_FLOAT_HEIGHT
+    };
+    let height_offset = height_offset.get_or_insert(target_offset);
+    height_offset.smooth_nudge(&target_offset, DECAY_RATE, time.delta_secs());
+
+    player_camera_parent.translation = player_transform.translation + Vec3::Y * *height_offset;
+}
+
+fn apply_fov_setting(
+    settings: Res<Settings>,
+    mut q_projection: Query<&mut Projection, With<WorldModelCamera>>,
+) {
+    for mut projection in &mut q_projection {
+        if let Projection::Perspective(perspective) = projection.as_mut() {
+            perspective.fov = settings.fov.to_radians();
+        }
+    }
+}
+
+fn apply_sensitivity_setting(
+    settings: Res<Settings>,
+    mut q_sensitivity: Query<&mut CameraSensitivity>,
 ) {
-    let camera_height = 1.84;
-    player_camera_parent.translation =
-        player.translation + Vec3::Y * (camera_height - PLAYER_FLOAT_HEIGHT);
+    for mut sensitivity in &mut q_sensitivity {
+        *sensitivity = CameraSensitivity::from_settings(&settings);
+    }
 }
 
 fn add_render_layers_to_point_light(trigger: Trigger<OnAdd, PointLight>, mut commands: Commands) {
@@ -236,3 +313,29 @@ fn add_render_layers_to_point_light(trigger: Trigger<OnAdd, PointLight>, mut com
         RenderLayer::DEFAULT | RenderLayer::VIEW_MODEL,
     ));
 }
+
+fn look_at_camera_target(
+    mut commands: Commands,
+    camera_target: Res<CameraTarget>,
+    q_transform: Query<&GlobalTransform, Without<PlayerCameraParent>>,
+    mut camera_parent: Single<&mut Transform, With<PlayerCameraParent>>,
+    time: Res<Time>,
+) {
+    // How quickly the camera turns to the target, see `StableInterpolate::smooth_nudge`.
+    const DECAY_RATE: f32 = 4.0;
+
+    let Ok(target) = q_transform.get(camera_target.0) else {
+        commands.remove_resource::<CameraTarget>();
+        return;
+    };
+    let target_rotation = camera_parent
+        .looking_at(target.translation(), Vec3::Y)
+        .rotation;
+    camera_parent
+        .rotation
+        .smooth_nudge(&target_rotation, DECAY_RATE, time.delta_secs());
+}
+
+fn forget_camera_target(mut commands: Commands) {
+    commands.remove_resource::<CameraTarget>();
+}
@@ -0,0 +1,229 @@
+//! Zooming moves the world model camera between first person, third person and top-down views.
+//! The [`PlayerCameraParent`] always stays at the player's eyes, so interacting with the world
+//! works the same in every mode.
+//!
+//! Outside of first person, the player would be invisible, as the arm only exists on the view model layer.
+//! Until the player has a proper character model, a capsule the size of its collider stands in for its body.
+
+use std::any::Any as _;
+
+use avian3d::prelude::*;
+use bevy::{math::StableInterpolate as _, prelude::*};
+use bevy_enhanced_input::prelude::*;
+
+use crate::{
+    AppSet, gameplay::crosshair::CrosshairState, screens::Screen,
+    third_party::avian3d::CollisionLayer,
+};
+
+use super::{
+    PLAYER_CAPSULE_LENGTH, PLAYER_RADIUS, Player,
+    camera::{
+        PlayerCameraParent, ViewModelCamera, WorldModelCamera, sync_camera_translation_with_player,
+    },
+    default_input::Zoom,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<(CameraMode, PlayerBody)>();
+    app.add_observer(spawn_player_body);
+    app.add_observer(zoom_camera.param_warn_once());
+    app.add_systems(
+        Update,
+        (
+            update_world_model_camera.param_warn_once(),
+            toggle_view_model.param_warn_once(),
+            fit_body_to_collider,
+        )
+            .after(sync_camera_translation_with_player)
+            .run_if(in_state(Screen::Gameplay))
+            .in_set(AppSet::Update),
+    );
+}
+
+#[derive(Debug, Component, Reflect, Clone, Copy, PartialEq, Default)]
+#[reflect(Component, Default)]
+pub(crate) enum CameraMode {
+    #[default]
+    FirstPerson,
+    /// Orbit around the player at the given distance.
+    ThirdPerson { distance: f32 },
+    /// Look down on the player from above.
+    TopDown,
+}
+
+const MIN_THIRD_PERSON_DISTANCE: f32 = 1.5;
+const MAX_THIRD_PERSON_DISTANCE: f32 = 6.0;
+const TOP_DOWN_HEIGHT: f32 = 10.0;
+/// How far behind the player the top-down camera is placed, so that it looks at the player at an angle.
+const TOP_DOWN_BACK_OFFSET: f32 = 3.0;
+/// Distance to keep between the camera and any geometry that blocks the spring arm.
+const SPRING_ARM_MARGIN: f32 = 0.2;
+/// How quickly the camera moves to its new position, see `StableInterpolate::smooth_nudge`.
+const DECAY_RATE: f32 = 12.0;
+
+impl CameraMode {
+    /// Positive values zoom out, negative values zoom in.
+    fn zoom(self, delta: f32) -> Self {
+        match self {
+            Self::FirstPerson if delta > 0.0 => Self::ThirdPerson {
+                distance: MIN_THIRD_PERSON_DISTANCE,
+            },
+            Self::ThirdPerson { distance } => {
+                let distance = distance + delta;
+                if distance < MIN_THIRD_PERSON_DISTANCE {
+                    Self::FirstPerson
+                } else if distance > MAX_THIRD_PERSON_DISTANCE {
+                    Self::TopDown
+                } else {
+                    Self::ThirdPerson { distance }
+                }
+            }
+            Self::TopDown if delta < 0.0 => Self::ThirdPerson {
+                distance: MAX_THIRD_PERSON_DISTANCE,
+            },
+            mode => mode,
+        }
+    }
+}
+
+/// The stand-in body of the player that is only visible outside of first person.
+#[derive(Debug, Component, Reflect)]
+#[reflect(Component)]
+struct PlayerBody;
+
+fn spawn_player_body(
+    trigger: Trigger<OnAdd, Player>,
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    commands.entity(trigger.entity()).with_child((
+        Name::new("Player Body"),
+        PlayerBody,
+        Mesh3d(meshes.add(Capsule3d::new(PLAYER_RADIUS, PLAYER_CAPSULE_LENGTH))),
+        MeshMaterial3d(materials.add(Color::srgb(0.8, 0.5, 0.3))),
+        // We start in first person.
+        Visibility::Hidden,
+    ));
+}
+
+/// Keeps the body the same size as the collider, e.g. while crouching.
+fn fit_body_to_collider(
+    player: Option<Single<&Collider, (With<Player>, Changed<Collider>)>>,
+    body: Option<Single<&mut Mesh3d, With<PlayerBody>>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+) {
+    let (Some(collider), Some(mut body)) = (player, body) else {
+        return;
+    };
+    let Some(capsule) = collider.shape_scaled().as_capsule() else {
+        return;
+    };
+    body.0 = meshes.add(Capsule3d::new(capsule.radius, 2.0 * capsule.half_height()));
+}
+
+fn zoom_camera(trigger: Trigger<Fired<Zoom>>, mut camera_mode: Single<&mut CameraMode>) {
+    let new_mode = camera_mode.zoom(trigger.value);
+    if **camera_mode != new_mode {
+        **camera_mode = new_mode;
+    }
+}
+
+fn update_world_model_camera(
+    camera_parent: Single<(&Transform, &CameraMode), With<PlayerCameraParent>>,
+    mut world_model_camera: Single<
+        &mut Transform,
+        (With<WorldModelCamera>, Without<PlayerCameraParent>),
+    >,
+    spatial_query: SpatialQuery,
+    time: Res<Time>,
+) {
+    let (parent_transform, camera_mode) = camera_parent.into_inner();
+    let eyes = parent_transform.translation;
+    let yaw = parent_transform.rotation.to_euler(EulerRot::YXZ).0;
+    let yaw_quat = Quat::from_axis_angle(Vec3::Y, yaw);
+
+    let desired_offset = match camera_mode {
+        CameraMode::FirstPerson => Vec3::ZERO,
+        CameraMode::ThirdPerson { distance } => parent_transform.back() * *distance,
+        CameraMode::TopDown => {
+            Vec3::Y * TOP_DOWN_HEIGHT + yaw_quat * Vec3::Z * TOP_DOWN_BACK_OFFSET
+        }
+    };
+    let offset = spring_arm(eyes, desired_offset, &spatial_query);
+
+    let target = match camera_mode {
+        CameraMode::FirstPerson | CameraMode::ThirdPerson { .. } => {
+            Transform::from_translation(eyes + offset).with_rotation(parent_transform.rotation)
+        }
+        CameraMode::TopDown => {
+            Transform::from_translation(eyes + offset).looking_at(eyes, yaw_quat * Vec3::NEG_Z)
+        }
+    };
+    // The world model camera is a child of the camera parent, so we need its target in local space.
+    let target =
+        GlobalTransform::from(target).reparented_to(&GlobalTransform::from(*parent_transform));
+
+    let is_obstructed = offset.length_squared() < desired_offset.length_squared();
+    let is_moving_closer =
+        target.translation.length_squared() < world_model_camera.translation.length_squared();
+    if is_obstructed && is_moving_closer {
+        // Snap to the obstruction instead of smoothly clipping through it.
+        world_model_camera.translation = target.translation;
+    } else {
+        world_model_camera.translation.smooth_nudge(
+            &target.translation,
+            DECAY_RATE,
+            time.delta_secs(),
+        );
+    }
+    world_model_camera
+        .rotation
+        .smooth_nudge(&target.rotation, DECAY_RATE, time.delta_secs());
+}
+
+/// Shortens the `desired_offset` from `origin` so that it does not go through level geometry.
+fn spring_arm(origin: Vec3, desired_offset: Vec3, spatial_query: &SpatialQuery) -> Vec3 {
+    let Ok((direction, length)) = Dir3::new_and_length(desired_offset) else {
+        return Vec3::ZERO;
+    };
+    let hit = spatial_query.cast_ray(
+        origin,
+        direction,
+        length,
+        true,
+        &SpatialQueryFilter::from_mask(CollisionLayer::Default),
+    );
+    match hit {
+        Some(hit) => direction * (hit.distance - SPRING_ARM_MARGIN).max(0.0),
+        None => desired_offset,
+    }
+}
+
+/// The view model only makes sense in first person, the body only outside of it.
+fn toggle_view_model(
+    camera_mode: Option<Single<&CameraMode, Changed<CameraMode>>>,
+    mut view_model_camera: Single<&mut Camera, With<ViewModelCamera>>,
+    mut body: Single<&mut Visibility, With<PlayerBody>>,
+    mut crosshair: Single<&mut CrosshairState>,
+) {
+    let Some(camera_mode) = camera_mode else {
+        return;
+    };
+    let is_first_person = matches!(**camera_mode, CameraMode::FirstPerson);
+    view_model_camera.is_active = is_first_person;
+    **body = if is_first_person {
+        Visibility::Hidden
+    } else {
+        Visibility::Inherited
+    };
+
+    // Aiming from above does not line up with what the player sees.
+    let system_id = toggle_view_model.type_id();
+    if matches!(**camera_mode, CameraMode::TopDown) {
+        crosshair.wants_invisible.insert(system_id);
+    } else {
+        crosshair.wants_invisible.remove(&system_id);
+    }
+}
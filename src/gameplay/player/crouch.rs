@@ -0,0 +1,126 @@
+//! Crouching lowers the player's float height and shrinks their collider
+//! so that they can fit through crawl spaces.
+
+use avian3d::prelude::*;
+use bevy::prelude::*;
+use bevy_enhanced_input::prelude::*;
+use bevy_tnua::prelude::*;
+
+use crate::{AppSet, screens::Screen, third_party::avian3d::CollisionLayer};
+
+use super::{
+    PLAYER_CAPSULE_LENGTH, PLAYER_FLOAT_HEIGHT, PLAYER_RADIUS, Player, default_input::Crouch,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<CrouchState>();
+    app.add_observer(start_crouching)
+        .add_observer(stop_crouching);
+    app.add_systems(
+        Update,
+        update_crouch
+            .run_if(in_state(Screen::Gameplay))
+            .in_set(AppSet::Update),
+    );
+}
+
+/// How far the float height is lowered while crouching.
+const CROUCH_FLOAT_OFFSET: f32 = -0.5;
+/// The capsule length while crouching.
+/// Keep in mind that the bottom of the capsule must stay above the crouched float height,
+/// otherwise the collider would drag along the ground.
+const CROUCHED_CAPSULE_LENGTH: f32 = 0.2;
+pub(crate) const CROUCHED_FLOAT_HEIGHT: f32 = PLAYER_FLOAT_HEIGHT + CROUCH_FLOAT_OFFSET;
+/// Multiplier applied to the walking velocity while crouching.
+pub(crate) const CROUCH_SPEED_FACTOR: f32 = 0.5;
+
+#[derive(Debug, Component, Reflect, Default)]
+#[reflect(Component, Default)]
+pub(crate) struct CrouchState {
+    /// Whether the crouch button is currently held.
+    wants_to_crouch: bool,
+    /// Whether the player is currently crouched.
+    /// This can be true even if the button is released when there is no room to stand up.
+    is_crouched: bool,
+}
+
+impl CrouchState {
+    pub(crate) fn is_crouched(&self) -> bool {
+        self.is_crouched
+    }
+}
+
+fn start_crouching(trigger: Trigger<Started<Crouch>>, mut q_crouch: Query<&mut CrouchState>) {
+    let Ok(mut crouch) = q_crouch.get_mut(trigger.entity()) else {
+        return;
+    };
+    crouch.wants_to_crouch = true;
+}
+
+fn stop_crouching(trigger: Trigger<Completed<Crouch>>, mut q_crouch: Query<&mut CrouchState>) {
+    let Ok(mut crouch) = q_crouch.get_mut(trigger.entity()) else {
+        return;
+    };
+    crouch.wants_to_crouch = false;
+}
+
+fn update_crouch(
+    mut commands: Commands,
+    mut q_player: Query<
+        (
+            Entity,
+            &mut CrouchState,
+            &mut TnuaController,
+            &GlobalTransform,
+        ),
+        With<Player>,
+    >,
+    spatial_query: SpatialQuery,
+) {
+    for (entity, mut crouch, mut controller, transform) in &mut q_player {
+        let should_crouch = crouch.wants_to_crouch
+            || (crouch.is_crouched && !has_room_to_stand_up(entity, transform, &spatial_query));
+        if should_crouch != crouch.is_crouched {
+            crouch.is_crouched = should_crouch;
+            let capsule_length = if should_crouch {
+                CROUCHED_CAPSULE_LENGTH
+            } else {
+                PLAYER_CAPSULE_LENGTH
+            };
+            commands
+                .entity(entity)
+                .insert(Collider::capsule(PLAYER_RADIUS, capsule_length));
+        }
+
+        if crouch.is_crouched {
+            // Like the walk basis, the crouch action needs to be fed every frame.
+            controller.action(TnuaBuiltinCrouch {
+                float_offset: CROUCH_FLOAT_OFFSET,
+                ..default()
+            });
+        }
+    }
+}
+
+/// Casts the crouched collider upwards to check if the standing collider would fit.
+fn has_room_to_stand_up(
+    player: Entity,
+    transform: &GlobalTransform,
+    spatial_query: &SpatialQuery,
+) -> bool {
+    let crouched_top = CROUCHED_FLOAT_HEIGHT + CROUCHED_CAPSULE_LENGTH / 2.0 + PLAYER_RADIUS;
+    let standing_top = PLAYER_FLOAT_HEIGHT + PLAYER_CAPSULE_LENGTH / 2.0 + PLAYER_RADIUS;
+    // Only check against level geometry and static props, dynamic props can be pushed away.
+    let filter =
+        SpatialQueryFilter::from_mask(CollisionLayer::Default).with_excluded_entities([player]);
+    spatial_query
+        .cast_shape(
+            &Collider::capsule(PLAYER_RADIUS, CROUCHED_CAPSULE_LENGTH),
+            transform.translation(),
+            Quat::IDENTITY,
+            Dir3::Y,
+            &ShapeCastConfig::from_max_distance(standing_top - crouched_top),
+            &filter,
+        )
+        .is_none()
+}
@@ -1,10 +1,16 @@
 use bevy::prelude::*;
 use bevy_enhanced_input::prelude::*;
 
+use crate::settings::{Keybinds, Settings};
+
 pub(super) fn plugin(app: &mut App) {
     // Record directional input as movement controls.
     app.add_input_context::<DefaultInputContext>()
         .add_observer(default_binding); // Add observer to setup bindings.
+    app.add_systems(
+        Update,
+        rebuild_bindings.run_if(resource_changed::<Settings>),
+    );
 }
 
 // All actions should implement the `InputAction` trait.
@@ -18,6 +24,14 @@ pub(crate) struct Move;
 #[input_action(output = bool)]
 pub(crate) struct Jump;
 
+#[derive(Debug, InputAction)]
+#[input_action(output = bool)]
+pub(crate) struct Sprint;
+
+#[derive(Debug, InputAction)]
+#[input_action(output = bool)]
+pub(crate) struct Crouch;
+
 #[derive(Debug, InputAction)]
 #[input_action(output = bool)]
 pub(crate) struct Interact;
@@ -26,6 +40,10 @@ pub(crate) struct Interact;
 #[input_action(output = Vec2)]
 pub(crate) struct Rotate;
 
+#[derive(Debug, InputAction)]
+#[input_action(output = f32)]
+pub(crate) struct Zoom;
+
 #[derive(Debug, InputAction)]
 #[input_action(output = bool)]
 pub(crate) struct PickupProp;
@@ -37,12 +55,20 @@ pub(crate) struct DropProp;
 #[derive(Debug, InputContext, Default)]
 pub(crate) struct DefaultInputContext;
 
+/// Does nothing. Only exists to consume the inputs of contexts with a lower priority,
+/// see [`block_default_inputs`].
+#[derive(Debug, InputAction)]
+#[input_action(output = bool)]
+pub(crate) struct BlockInput;
+
 fn default_binding(
     trigger: Trigger<Binding<DefaultInputContext>>,
     mut players: Query<&mut Actions<DefaultInputContext>>,
+    settings: Res<Settings>,
 ) {
     const DEFAULT_SPEED: f32 = 10.0;
     let mut actions = players.get_mut(trigger.entity()).unwrap();
+    let keybinds = &settings.keybinds;
 
     // Mappings like WASD or sticks are very common,
     // so we provide built-ins to assign all keys/axes at once.
@@ -63,17 +89,43 @@ fn default_binding(
     // and the action will respond to any of them.
     actions
         .bind::<Jump>()
-        .to((KeyCode::Space, GamepadButton::South));
+        .to((keybinds.jump, GamepadButton::South));
+
+    actions
+        .bind::<Sprint>()
+        .to((keybinds.sprint, GamepadButton::LeftThumb));
+
+    actions
+        .bind::<Crouch>()
+        .to((keybinds.crouch, GamepadButton::RightThumb));
 
     actions
         .bind::<Interact>()
-        .to((KeyCode::KeyE, GamepadButton::South));
+        .to((keybinds.interact, GamepadButton::South));
 
-    const DEFAULT_SENSITIVITY: f32 = 0.002;
+    // The sensitivity is applied by `CameraSensitivity` so that it can be changed in the settings.
     actions
         .bind::<Rotate>()
         .to((Input::mouse_motion(), GamepadStick::Right))
-        .with_modifiers((Negate::all(), Scale::splat(DEFAULT_SENSITIVITY)));
+        .with_modifiers(Negate::all());
+
+    // Scrolling down or pressing down on the D-pad zooms out, up zooms in.
+    const ZOOM_STEP: f32 = 0.5;
+    actions.bind::<Zoom>().to((
+        Input::mouse_wheel().with_modifiers((
+            // The mouse wheel's vertical axis is its second component, but we need it as the first one.
+            SwizzleAxis::YXZ,
+            Negate::all(),
+            Scale::splat(ZOOM_STEP),
+        )),
+        // Buttons fire every frame while held, so we only zoom once per press.
+        GamepadButton::DPadDown
+            .with_modifiers(Scale::splat(ZOOM_STEP))
+            .with_conditions(Press::default()),
+        GamepadButton::DPadUp
+            .with_modifiers((Negate::all(), Scale::splat(ZOOM_STEP)))
+            .with_conditions(Press::default()),
+    ));
 
     actions
         .bind::<PickupProp>()
@@ -83,3 +135,41 @@ fn default_binding(
         .bind::<DropProp>()
         .to((MouseButton::Right, GamepadButton::East));
 }
+
+/// Binds every input the default context can use to [`BlockInput`],
+/// so that a context with a higher priority keeps the player from being controlled, e.g. while a menu is open.
+/// Call this after binding the context's own actions, so that those get to use the inputs first.
+///
+/// Instead of listing the default bindings a second time, this blocks all mouse and gamepad inputs.
+/// On the keyboard, the default context only uses WASD and the [`Keybinds`].
+pub(crate) fn block_default_inputs<C: InputContext>(actions: &mut Actions<C>, keybinds: &Keybinds) {
+    actions
+        .bind::<BlockInput>()
+        .to((
+            Cardinal::wasd_keys(),
+            Input::mouse_motion(),
+            Input::mouse_wheel(),
+            MouseButton::Left,
+            MouseButton::Right,
+            MouseButton::Middle,
+            MouseButton::Back,
+            MouseButton::Forward,
+            GamepadStick::Left,
+            GamepadStick::Right,
+        ))
+        .to(keybinds.keys())
+        .to(GamepadButton::all());
+}
+
+/// Apply changed keybinds by rebuilding the bindings of all input contexts.
+fn rebuild_bindings(
+    mut commands: Commands,
+    settings: Res<Settings>,
+    mut applied_keybinds: Local<Option<Keybinds>>,
+) {
+    if applied_keybinds.as_ref() == Some(&settings.keybinds) {
+        return;
+    }
+    *applied_keybinds = Some(settings.keybinds.clone());
+    commands.trigger(RebuildBindings);
+}
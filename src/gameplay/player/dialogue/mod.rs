@@ -8,17 +8,15 @@ use crate::{
     screens::Screen,
     third_party::{
         avian3d::CollisionLayer,
-        bevy_yarnspinner::{YarnNode, is_dialogue_running},
+        bevy_yarnspinner::{InDialogue, YarnNode, is_dialogue_running},
     },
 };
 
 mod ui;
 
 use super::{
-    Player,
-    camera::PlayerCameraParent,
-    default_input::{DefaultInputContext, Interact},
-    pickup::is_holding_prop,
+    Player, camera::PlayerCameraParent, default_input::Interact,
+    dialogue_input::DialogueInputContext, pickup::is_holding_prop,
 };
 
 pub(super) fn plugin(app: &mut App) {
@@ -42,7 +40,7 @@ pub(super) fn plugin(app: &mut App) {
     );
     app.add_systems(
         Update,
-        restore_input_context
+        pop_dialogue_input_context
             .param_warn_once()
             .run_if(in_state(Screen::Gameplay))
             .run_if(on_event::<DialogueCompleteEvent>)
@@ -75,9 +73,12 @@ fn check_for_dialogue_opportunity(
         true,
         &SpatialQueryFilter::from_mask(CollisionLayer::Default),
     );
-    let node = hit
-        .and_then(|hit| q_yarn_node.get(hit.entity).ok())
-        .cloned();
+    let node = hit.and_then(|hit| {
+        q_yarn_node
+            .get(hit.entity)
+            .ok()
+            .map(|node| (hit.entity, node.clone()))
+    });
     if interaction_prompt.0 != node {
         interaction_prompt.0 = node;
     }
@@ -85,7 +86,7 @@ fn check_for_dialogue_opportunity(
 
 #[derive(Component, Default, Reflect)]
 #[reflect(Component, Default)]
-struct InteractionPrompt(Option<YarnNode>);
+struct InteractionPrompt(Option<(Entity, YarnNode)>);
 
 fn interact_with_dialogue(
     _trigger: Trigger<Started<Interact>>,
@@ -94,17 +95,19 @@ fn interact_with_dialogue(
     mut dialogue_runner: Single<&mut DialogueRunner>,
     player: Single<Entity, With<Player>>,
 ) {
-    let Some(node) = interaction_prompt.0.take() else {
+    let Some((speaker, node)) = interaction_prompt.0.take() else {
         return;
     };
     dialogue_runner.start_node(&node.yarn_node);
+    commands.entity(speaker).insert(InDialogue);
+    // Takes precedence over the default input context until the dialogue is done.
     commands
         .entity(*player)
-        .remove::<Actions<DefaultInputContext>>();
+        .insert(Actions::<DialogueInputContext>::default());
 }
 
-fn restore_input_context(mut commands: Commands, player: Single<Entity, With<Player>>) {
+fn pop_dialogue_input_context(mut commands: Commands, player: Single<Entity, With<Player>>) {
     commands
         .entity(*player)
-        .insert(Actions::<DefaultInputContext>::default());
+        .remove::<Actions<DialogueInputContext>>();
 }
@@ -0,0 +1,104 @@
+//! Input while talking to someone.
+//!
+//! [`DialogueInputContext`] is added to the player when a dialogue starts and removed when it ends.
+//! It has a higher priority than [`DefaultInputContext`](super::default_input::DefaultInputContext)
+//! and consumes all gameplay inputs, so the player stands still while the dialogue is running
+//! without losing the state of their default bindings.
+
+use bevy::prelude::*;
+use bevy_enhanced_input::prelude::*;
+
+use crate::settings::Settings;
+
+use super::default_input::{BlockInput, block_default_inputs};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_input_context::<DialogueInputContext>();
+    app.add_observer(dialogue_binding);
+}
+
+/// Continue to the next line, or confirm the selected option.
+#[derive(Debug, InputAction)]
+#[input_action(output = bool)]
+pub(crate) struct AdvanceDialogue;
+
+/// Show the rest of the line that is being typed.
+#[derive(Debug, InputAction)]
+#[input_action(output = bool)]
+pub(crate) struct SkipLine;
+
+/// Pick an option by its number.
+#[derive(Debug, InputAction)]
+#[input_action(output = f32)]
+pub(crate) struct SelectOption;
+
+/// Move the selection up (positive) or down (negative) the list of options.
+#[derive(Debug, InputAction)]
+#[input_action(output = f32)]
+pub(crate) struct NavigateOptions;
+
+#[derive(Debug, InputContext, Default)]
+#[input_context(priority = 1)]
+pub(crate) struct DialogueInputContext;
+
+fn dialogue_binding(
+    trigger: Trigger<Binding<DialogueInputContext>>,
+    mut q_actions: Query<&mut Actions<DialogueInputContext>>,
+    settings: Res<Settings>,
+) {
+    let mut actions = q_actions.get_mut(trigger.entity()).unwrap();
+    let keybinds = &settings.keybinds;
+
+    actions
+        .bind::<AdvanceDialogue>()
+        .to((keybinds.interact, ADVANCE_KEYS, GamepadButton::South));
+
+    actions
+        .bind::<SkipLine>()
+        .to((SKIP_KEY, GamepadButton::East));
+
+    for (index, key) in NUMBER_KEYS.into_iter().enumerate() {
+        // Each key reports the number of its option.
+        actions
+            .bind::<SelectOption>()
+            .to(key.with_modifiers(Scale::splat(index as f32 + 1.0)));
+    }
+
+    actions.bind::<NavigateOptions>().to((
+        UP_KEY,
+        KeyCode::KeyW,
+        GamepadButton::DPadUp,
+        DOWN_KEY.with_modifiers(Negate::all()),
+        KeyCode::KeyS.with_modifiers(Negate::all()),
+        GamepadButton::DPadDown.with_modifiers(Negate::all()),
+        // The stick's vertical axis is its second component, but we need it as the first one.
+        GamepadStick::Left.with_modifiers((DeadZone::default(), SwizzleAxis::YXZ)),
+    ));
+
+    // Bound last, so that the actions above get to use these inputs first.
+    block_default_inputs(&mut actions, keybinds);
+}
+
+// The keys of the dialogue context that the default context does not use.
+const ADVANCE_KEYS: [KeyCode; 2] = [KeyCode::Space, KeyCode::Enter];
+const SKIP_KEY: KeyCode = KeyCode::Tab;
+const NUMBER_KEYS: [KeyCode; 9] = [
+    KeyCode::Digit1,
+    KeyCode::Digit2,
+    KeyCode::Digit3,
+    KeyCode::Digit4,
+    KeyCode::Digit5,
+    KeyCode::Digit6,
+    KeyCode::Digit7,
+    KeyCode::Digit8,
+    KeyCode::Digit9,
+];
+const UP_KEY: KeyCode = KeyCode::ArrowUp;
+const DOWN_KEY: KeyCode = KeyCode::ArrowDown;
+
+/// Like [`block_default_inputs`], but for the inputs of the dialogue context.
+pub(crate) fn block_dialogue_inputs<C: InputContext>(actions: &mut Actions<C>) {
+    actions
+        .bind::<BlockInput>()
+        .to((ADVANCE_KEYS, SKIP_KEY, NUMBER_KEYS, UP_KEY, DOWN_KEY));
+}
@@ -13,30 +13,43 @@ use bevy_landmass::{Character, prelude::*};
 use bevy_tnua::{TnuaAnimatingState, prelude::*};
 use bevy_tnua_avian3d::TnuaAvian3dSensorShape;
 use bevy_trenchbroom::prelude::*;
+use crouch::CrouchState;
 use default_input::DefaultInputContext;
+use stamina::Stamina;
 
-use crate::third_party::{avian3d::CollisionLayer, bevy_trenchbroom::fix_gltf_rotation};
+use crate::{
+    gameplay::{footstep::Strides, surface::GroundSurface},
+    third_party::{avian3d::CollisionLayer, bevy_trenchbroom::fix_gltf_rotation},
+};
 
 mod animation;
 pub(crate) mod assets;
 pub(crate) mod camera;
+pub(crate) mod camera_mode;
+pub(crate) mod crouch;
 pub(crate) mod default_input;
 pub(crate) mod dialogue;
+pub(crate) mod dialogue_input;
 pub(crate) mod movement;
 pub mod movement_sound;
 pub(crate) mod pickup;
+pub(crate) mod stamina;
 
 pub(super) fn plugin(app: &mut App) {
     app.register_type::<Player>();
     app.add_plugins((
         assets::plugin,
         default_input::plugin,
+        dialogue_input::plugin,
         movement::plugin,
         camera::plugin,
+        camera_mode::plugin,
+        crouch::plugin,
         dialogue::plugin,
         pickup::plugin,
         animation::plugin,
         movement_sound::plugin,
+        stamina::plugin,
     ));
     app.add_observer(setup_player_character);
 }
@@ -51,7 +64,10 @@ pub(super) fn plugin(app: &mut App) {
 pub(crate) struct Player;
 
 pub(crate) const PLAYER_RADIUS: f32 = 0.5;
+const PLAYER_CAPSULE_LENGTH: f32 = 1.0;
 const PLAYER_FLOAT_HEIGHT: f32 = 1.3;
+/// Makes for about five footsteps per second at walking speed.
+const PLAYER_STRIDE_LENGTH: f32 = 2.0;
 
 impl Player {
     fn on_add(mut world: DeferredWorld, entity: Entity, _id: ComponentId) {
@@ -67,7 +83,7 @@ impl Player {
                 Actions::<DefaultInputContext>::default(),
                 // The player character needs to be configured as a dynamic rigid body of the physics
                 // engine.
-                Collider::capsule(PLAYER_RADIUS, 1.0),
+                Collider::capsule(PLAYER_RADIUS, PLAYER_CAPSULE_LENGTH),
                 // This is Tnua's interface component.
                 TnuaController::default(),
                 // A sensor shape is not strictly necessary, but without it we'll get weird results.
@@ -85,6 +101,12 @@ impl Player {
                 TransformInterpolation,
                 CollisionLayers::new(CollisionLayer::Player, LayerMask::ALL),
                 TnuaAnimatingState::<PlayerAnimationState>::default(),
+                (
+                    Stamina::default(),
+                    CrouchState::default(),
+                    GroundSurface::default(),
+                    Strides::new(PLAYER_STRIDE_LENGTH),
+                ),
             ))
             .observe(setup_player_animations);
     }
@@ -5,6 +5,8 @@ use bevy_tnua::prelude::*;
 use super::default_input::{Jump, Move};
 
 use super::PLAYER_FLOAT_HEIGHT;
+use super::crouch::{CROUCH_SPEED_FACTOR, CrouchState};
+use super::stamina::{SPRINT_SPEED_FACTOR, Stamina};
 use super::{Player, camera::PlayerCameraParent};
 
 pub(super) fn plugin(app: &mut App) {
@@ -24,10 +26,10 @@ fn reset_movement(mut controllers: Query<&mut TnuaController, With<Player>>) {
 
 fn apply_movement(
     trigger: Trigger<Fired<Move>>,
-    mut controllers: Query<&mut TnuaController, With<Player>>,
+    mut controllers: Query<(&mut TnuaController, &Stamina, &CrouchState), With<Player>>,
     transform: Single<&Transform, With<PlayerCameraParent>>,
 ) {
-    let Ok(mut controller) = controllers.get_mut(trigger.entity()) else {
+    let Ok((mut controller, stamina, crouch)) = controllers.get_mut(trigger.entity()) else {
         error!("Triggered movement for entity with missing components");
         return;
     };
@@ -36,9 +38,16 @@ fn apply_movement(
     // just fall.
     let yaw = transform.rotation.to_euler(EulerRot::YXZ).0;
     let yaw_quat = Quat::from_axis_angle(Vec3::Y, yaw);
+    let speed_factor = if crouch.is_crouched() {
+        CROUCH_SPEED_FACTOR
+    } else if stamina.is_sprinting() {
+        SPRINT_SPEED_FACTOR
+    } else {
+        1.0
+    };
     controller.basis(TnuaBuiltinWalk {
         // The `desired_velocity` determines how the character will move.
-        desired_velocity: yaw_quat * trigger.value,
+        desired_velocity: yaw_quat * trigger.value * speed_factor,
         // The `float_height` must be greater (even if by little) from the distance between the
         // character's center and the lowest point of its collider.
         float_height: PLAYER_FLOAT_HEIGHT,
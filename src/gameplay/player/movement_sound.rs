@@ -1,29 +1,34 @@
-use std::time::Duration;
-
-use avian3d::prelude::LinearVelocity;
 use bevy::prelude::*;
 use bevy_tnua::{builtins::TnuaBuiltinJumpState, prelude::*};
 
-use crate::{AppSet, audio::SoundEffect, screens::Screen};
+use crate::{
+    AppSet,
+    audio::SoundEffect,
+    gameplay::{footstep::Footstep, material_sound::MaterialSoundAssets, surface::GroundSurface},
+    screens::Screen,
+};
 
-use super::{Player, assets::PlayerAssets};
+use super::{Player, assets::PlayerAssets, crouch::CrouchState, stamina::Stamina};
 
 pub(super) fn plugin(app: &mut App) {
     app.add_systems(
         Update,
-        (play_jump_grunt, play_step_sound, play_land_sound)
+        (play_jump_grunt, play_land_sound)
             .run_if(in_state(Screen::Gameplay))
             .in_set(AppSet::PlaySounds),
     );
+    app.add_observer(play_step_sound);
 }
 
 fn play_jump_grunt(
     mut commands: Commands,
-    player: Single<&TnuaController, With<Player>>,
+    player: Single<(&TnuaController, &GroundSurface), With<Player>>,
     mut player_assets: ResMut<PlayerAssets>,
+    mut material_sounds: ResMut<MaterialSoundAssets>,
     mut is_jumping: Local<bool>,
 ) {
-    let Some((_jump, jump_state)) = player.concrete_action::<TnuaBuiltinJump>() else {
+    let (controller, surface) = player.into_inner();
+    let Some((_jump, jump_state)) = controller.concrete_action::<TnuaBuiltinJump>() else {
         return;
     };
     let started_jumping = matches!(
@@ -42,7 +47,8 @@ fn play_jump_grunt(
 
     let rng = &mut rand::thread_rng();
     let grunt = player_assets.jump_grunts.pick(rng).clone();
-    let jump_start = player_assets.jump_start_sounds.pick(rng).clone();
+    let surface_sounds = surface.0.sounds(&mut material_sounds);
+    let jump_start = surface_sounds.jump_starts.pick(rng).clone();
 
     commands.spawn((
         AudioPlayer(grunt.clone()),
@@ -51,49 +57,44 @@ fn play_jump_grunt(
     ));
     commands.spawn((
         AudioPlayer(jump_start.clone()),
-        PlaybackSettings::DESPAWN,
+        PlaybackSettings::DESPAWN.with_speed(surface_sounds.speed),
         SoundEffect,
     ));
 }
 
 fn play_step_sound(
+    trigger: Trigger<Footstep>,
     mut commands: Commands,
-    player: Single<(&TnuaController, &LinearVelocity), With<Player>>,
-    mut player_assets: ResMut<PlayerAssets>,
-    time: Res<Time>,
-    mut timer: Local<Option<Timer>>,
+    q_player: Query<(&Stamina, &CrouchState, &GroundSurface), With<Player>>,
+    mut material_sounds: ResMut<MaterialSoundAssets>,
 ) {
-    let timer =
-        timer.get_or_insert_with(|| Timer::new(Duration::from_millis(200), TimerMode::Repeating));
-    timer.tick(time.delta());
-    if !timer.finished() {
-        return;
-    }
-
-    let (controller, linear_velocity) = player.into_inner();
-    if controller.is_airborne().unwrap_or(true) {
-        return;
-    }
-    if linear_velocity.length_squared() < 5.0 {
+    let Ok((stamina, crouch, surface)) = q_player.get(trigger.entity()) else {
         return;
-    }
+    };
+    let is_sprinting = stamina.is_sprinting_while(crouch);
     let rng = &mut rand::thread_rng();
-    let sound_effect = player_assets.steps.pick(rng).clone();
+    let surface_sounds = surface.0.sounds(&mut material_sounds);
+    let sound_effect = if is_sprinting {
+        surface_sounds.run_steps.pick(rng).clone()
+    } else {
+        surface_sounds.steps.pick(rng).clone()
+    };
 
     commands.spawn((
         AudioPlayer(sound_effect.clone()),
-        PlaybackSettings::DESPAWN,
+        PlaybackSettings::DESPAWN.with_speed(surface_sounds.speed),
         SoundEffect,
     ));
 }
 
 fn play_land_sound(
     mut commands: Commands,
-    player: Single<&TnuaController, With<Player>>,
-    mut player_assets: ResMut<PlayerAssets>,
+    player: Single<(&TnuaController, &GroundSurface), With<Player>>,
+    mut material_sounds: ResMut<MaterialSoundAssets>,
     mut was_airborne: Local<bool>,
 ) {
-    let is_airborne = player.is_airborne().unwrap_or(true);
+    let (controller, surface) = player.into_inner();
+    let is_airborne = controller.is_airborne().unwrap_or(true);
     if is_airborne {
         *was_airborne = true;
         return;
@@ -104,11 +105,12 @@ fn play_land_sound(
     *was_airborne = false;
 
     let rng = &mut rand::thread_rng();
-    let sound_effect = player_assets.land_sounds.pick(rng).clone();
+    let surface_sounds = surface.0.sounds(&mut material_sounds);
+    let sound_effect = surface_sounds.lands.pick(rng).clone();
 
     commands.spawn((
         AudioPlayer(sound_effect.clone()),
-        PlaybackSettings::DESPAWN,
+        PlaybackSettings::DESPAWN.with_speed(surface_sounds.speed),
         SoundEffect,
     ));
 }
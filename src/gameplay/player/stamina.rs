@@ -0,0 +1,107 @@
+//! Sprinting drains the player's stamina, which refills over time while not sprinting.
+
+use avian3d::prelude::LinearVelocity;
+use bevy::prelude::*;
+use bevy_enhanced_input::prelude::*;
+
+use crate::{AppSet, screens::Screen};
+
+use super::{Player, crouch::CrouchState, default_input::Sprint};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<Stamina>();
+    app.add_observer(start_sprinting)
+        .add_observer(stop_sprinting);
+    app.add_systems(
+        Update,
+        update_stamina
+            .run_if(in_state(Screen::Gameplay))
+            .in_set(AppSet::Update),
+    );
+}
+
+/// Multiplier applied to the walking velocity while sprinting.
+pub(crate) const SPRINT_SPEED_FACTOR: f32 = 1.6;
+
+/// Stamina drained per second of sprinting.
+const DRAIN_PER_SECOND: f32 = 25.0;
+/// Stamina refilled per second while not sprinting.
+const REFILL_PER_SECOND: f32 = 15.0;
+/// After running out of stamina, the player needs to recover this fraction of
+/// their maximum stamina before they can sprint again.
+/// This prevents the player from stuttering between sprinting and walking.
+const RECOVERY_THRESHOLD: f32 = 0.3;
+/// Squared horizontal speed below which the player does not count as moving.
+const MIN_MOVING_SPEED_SQUARED: f32 = 1.0;
+
+#[derive(Debug, Component, Reflect)]
+#[reflect(Component)]
+pub(crate) struct Stamina {
+    pub(crate) current: f32,
+    pub(crate) max: f32,
+    /// Whether the sprint button is currently held.
+    wants_to_sprint: bool,
+    /// Set when the stamina ran out. Cleared again once [`RECOVERY_THRESHOLD`] is reached.
+    exhausted: bool,
+}
+
+impl Default for Stamina {
+    fn default() -> Self {
+        Self {
+            current: 100.0,
+            max: 100.0,
+            wants_to_sprint: false,
+            exhausted: false,
+        }
+    }
+}
+
+impl Stamina {
+    pub(crate) fn is_sprinting(&self) -> bool {
+        self.wants_to_sprint && !self.exhausted
+    }
+
+    /// Like [`Stamina::is_sprinting`], but also takes into account that crouching prevents sprinting.
+    pub(crate) fn is_sprinting_while(&self, crouch: &CrouchState) -> bool {
+        self.is_sprinting() && !crouch.is_crouched()
+    }
+
+    pub(crate) fn fraction(&self) -> f32 {
+        self.current / self.max
+    }
+}
+
+fn start_sprinting(trigger: Trigger<Started<Sprint>>, mut q_stamina: Query<&mut Stamina>) {
+    let Ok(mut stamina) = q_stamina.get_mut(trigger.entity()) else {
+        return;
+    };
+    stamina.wants_to_sprint = true;
+}
+
+fn stop_sprinting(trigger: Trigger<Completed<Sprint>>, mut q_stamina: Query<&mut Stamina>) {
+    let Ok(mut stamina) = q_stamina.get_mut(trigger.entity()) else {
+        return;
+    };
+    stamina.wants_to_sprint = false;
+}
+
+fn update_stamina(
+    time: Res<Time>,
+    mut q_player: Query<(&mut Stamina, &CrouchState, &LinearVelocity), With<Player>>,
+) {
+    for (mut stamina, crouch, linear_velocity) in &mut q_player {
+        let is_moving = linear_velocity.xz().length_squared() > MIN_MOVING_SPEED_SQUARED;
+        if stamina.is_sprinting_while(crouch) && is_moving {
+            stamina.current = (stamina.current - DRAIN_PER_SECOND * time.delta_secs()).max(0.0);
+            if stamina.current <= 0.0 {
+                stamina.exhausted = true;
+            }
+        } else {
+            stamina.current =
+                (stamina.current + REFILL_PER_SECOND * time.delta_secs()).min(stamina.max);
+            if stamina.exhausted && stamina.fraction() >= RECOVERY_THRESHOLD {
+                stamina.exhausted = false;
+            }
+        }
+    }
+}
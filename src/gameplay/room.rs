@@ -0,0 +1,59 @@
+//! Named areas of a level. Dialogue can check which rooms the player has visited.
+
+use avian3d::prelude::*;
+use bevy::{prelude::*, utils::HashSet};
+use bevy_trenchbroom::prelude::*;
+
+use crate::{AppSet, screens::Screen, third_party::bevy_trenchbroom::hidden};
+
+use super::player::Player;
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<(Room, VisitedRooms)>();
+    app.init_resource::<VisitedRooms>();
+    app.add_systems(OnEnter(Screen::Title), forget_visited_rooms);
+    app.add_systems(
+        Update,
+        visit_room
+            .run_if(in_state(Screen::Gameplay).and(on_event::<CollisionStarted>))
+            .in_set(AppSet::Update),
+    );
+}
+
+/// A trigger volume that marks its `name` as visited when the player enters it.
+#[derive(SolidClass, Component, Debug, Clone, Default, Reflect)]
+#[reflect(Component, Default, Debug)]
+#[require(Sensor, Visibility(hidden))]
+#[geometry(GeometryProvider::new().convex_collider())]
+// In Wasm, TrenchBroom classes are not automatically registered.
+// So, we need to manually register the class in `src/third_party/bevy_trenchbroom/mod.rs`.
+pub(crate) struct Room {
+    pub(crate) name: String,
+}
+
+/// The names of all rooms the player has entered during this playthrough.
+#[derive(Resource, Debug, Clone, Default, Reflect)]
+#[reflect(Resource)]
+pub(crate) struct VisitedRooms(pub(crate) HashSet<String>);
+
+fn forget_visited_rooms(mut visited_rooms: ResMut<VisitedRooms>) {
+    visited_rooms.0.clear();
+}
+
+fn visit_room(
+    mut collision_started: EventReader<CollisionStarted>,
+    q_room: Query<&Room>,
+    q_player: Query<(), With<Player>>,
+    mut visited_rooms: ResMut<VisitedRooms>,
+) {
+    for CollisionStarted(a, b) in collision_started.read() {
+        let room = match (q_room.get(*a), q_room.get(*b)) {
+            (Ok(room), _) if q_player.contains(*b) => room,
+            (_, Ok(room)) if q_player.contains(*a) => room,
+            _ => continue,
+        };
+        if !visited_rooms.0.contains(&room.name) {
+            visited_rooms.0.insert(room.name.clone());
+        }
+    }
+}
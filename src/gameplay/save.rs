@@ -0,0 +1,462 @@
+//! Saving and loading the state of the level.
+//!
+//! The level itself is always spawned from its map, so a save only contains the things that can change
+//! during gameplay. Loading a save spawns the level as usual and then applies the saved state on top of it.
+//! Props that are not part of the map, e.g. items given by dialogue, are saved by their classname and spawned again.
+//! Props of the map that were broken are despawned again.
+//!
+//! Props and NPCs of the map are identified by a [`SaveKey`] taken from the map, so that they are found again
+//! in later sessions. Saves are only loaded into the exact map they were made in, as anything else could
+//! put the player inside of a wall.
+
+use std::collections::HashMap;
+
+use avian3d::prelude::*;
+use bevy::prelude::*;
+use bevy_trenchbroom::{
+    class::{ErasedQuakeClass, QuakeClass, QuakeClassSpawnView},
+    config::TrenchBroomConfig,
+    qmap::QuakeMapEntity,
+};
+use bevy_yarnspinner::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    AppSet,
+    props::{DynamicProp, Prop, PropBroken},
+    screens::Screen,
+    storage,
+    third_party::bevy_trenchbroom::target::TargetName,
+};
+
+use super::{
+    door::{Door, DoorState},
+    level::{CurrentLevel, LevelId, LevelRegistry},
+    npc::Npc,
+    player::{Player, camera::PlayerCameraParent},
+    room::VisitedRooms,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<(SaveKey, SpawnedProp)>();
+    app.init_resource::<BrokenProps>();
+    app.add_observer(record_broken_prop);
+    app.add_systems(OnEnter(Screen::SpawnLevel), reset_broken_props);
+    app.add_systems(
+        Update,
+        apply_pending_save
+            .run_if(in_state(Screen::Gameplay).and(resource_exists::<PendingSave>))
+            .in_set(AppSet::Update),
+    );
+}
+
+const STORAGE_NAME: &str = "save";
+
+/// Identifies a prop or NPC of the map across sessions.
+/// This is its `targetname` if it has one, and otherwise its classname and position in the map,
+/// so that editing other parts of the map does not change it.
+#[derive(Debug, Component, Reflect, Clone, PartialEq, Eq, Hash)]
+#[reflect(Component)]
+pub(crate) struct SaveKey(String);
+
+impl SaveKey {
+    pub(crate) fn from_map_entity(entity: &QuakeMapEntity) -> Self {
+        let property = |key: &str| {
+            entity
+                .properties
+                .get(key)
+                .map(String::as_str)
+                .unwrap_or_default()
+        };
+        let targetname = property("targetname");
+        if targetname.is_empty() {
+            Self(format!(
+                "{} at {}",
+                property("classname"),
+                property("origin")
+            ))
+        } else {
+            Self(targetname.to_string())
+        }
+    }
+}
+
+pub(crate) trait RegisterSavedClass {
+    /// Like [`TrenchBroomConfig::register_class`], but also gives the spawned entities a [`SaveKey`].
+    fn register_saved_class<T: QuakeClass>(self) -> TrenchBroomConfig;
+}
+
+impl RegisterSavedClass for TrenchBroomConfig {
+    fn register_saved_class<T: QuakeClass>(mut self) -> TrenchBroomConfig {
+        // Same as for the props, this is only done once at startup.
+        let class = Box::leak(Box::new(ErasedQuakeClass {
+            spawn_fn: spawn_saved_class::<T>,
+            ..ErasedQuakeClass::of::<T>()
+        }));
+        self.class_map.insert(class.info.name, class);
+        self
+    }
+}
+
+fn spawn_saved_class<T: QuakeClass>(
+    config: &TrenchBroomConfig,
+    src_entity: &QuakeMapEntity,
+    view: &mut QuakeClassSpawnView,
+) -> anyhow::Result<()> {
+    (ErasedQuakeClass::of::<T>().spawn_fn)(config, src_entity, view)?;
+    view.world
+        .entity_mut(view.entity)
+        .insert(SaveKey::from_map_entity(src_entity));
+    Ok(())
+}
+
+/// A prop that was spawned during gameplay instead of from the map, e.g. by `<<give_item>>`.
+/// Such props are saved by their classname instead of a [`SaveKey`].
+#[derive(Debug, Component, Reflect)]
+#[reflect(Component)]
+pub(crate) struct SpawnedProp;
+
+/// The [`SaveKey`]s of the props of the map that have been broken.
+#[derive(Debug, Resource, Default)]
+struct BrokenProps(Vec<String>);
+
+fn reset_broken_props(mut broken_props: ResMut<BrokenProps>) {
+    broken_props.0.clear();
+}
+
+fn record_broken_prop(
+    trigger: Trigger<PropBroken>,
+    q_save_key: Query<&SaveKey>,
+    mut broken_props: ResMut<BrokenProps>,
+) {
+    if let Ok(key) = q_save_key.get(trigger.entity()) {
+        broken_props.0.push(key.0.clone());
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct SaveGame {
+    /// Saves from before multiple levels existed were always made in the default level.
+    #[serde(default)]
+    pub(crate) level: LevelId,
+    /// The [`LevelInfo::map_hash`](super::level::LevelInfo::map_hash) of the level when it was saved.
+    /// Saves from before this was recorded have none and can't be loaded.
+    #[serde(default)]
+    map_hash: Option<u64>,
+    player: PlayerSave,
+    /// Also contains the NPCs, which are physics bodies just like the props.
+    props: Vec<PropSave>,
+    variables: HashMap<String, SavedValue>,
+    #[serde(default)]
+    visited_rooms: Vec<String>,
+    #[serde(default)]
+    spawned_props: Vec<SpawnedPropSave>,
+    /// The `targetname`s of all open doors.
+    #[serde(default)]
+    open_doors: Vec<String>,
+    /// The [`SaveKey`]s of the props of the map that have been broken.
+    #[serde(default)]
+    broken_props: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct PlayerSave {
+    translation: Vec3,
+    yaw: f32,
+    pitch: f32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct PropSave {
+    key: String,
+    transform: Transform,
+    linear_velocity: Vec3,
+    angular_velocity: Vec3,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct SpawnedPropSave {
+    classname: String,
+    transform: Transform,
+    linear_velocity: Vec3,
+    angular_velocity: Vec3,
+}
+
+/// A serializable version of [`YarnValue`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+enum SavedValue {
+    Number(f32),
+    String(String),
+    Boolean(bool),
+}
+
+impl From<YarnValue> for SavedValue {
+    fn from(value: YarnValue) -> Self {
+        match value {
+            YarnValue::Number(number) => Self::Number(number),
+            YarnValue::String(string) => Self::String(string),
+            YarnValue::Boolean(boolean) => Self::Boolean(boolean),
+        }
+    }
+}
+
+impl From<SavedValue> for YarnValue {
+    fn from(value: SavedValue) -> Self {
+        match value {
+            SavedValue::Number(number) => Self::Number(number),
+            SavedValue::String(string) => Self::String(string),
+            SavedValue::Boolean(boolean) => Self::Boolean(boolean),
+        }
+    }
+}
+
+impl SaveGame {
+    /// Loads the save game, unless its level's map has changed since it was made.
+    pub(crate) fn load(registry: &LevelRegistry) -> Option<Self> {
+        if !storage::exists(STORAGE_NAME) {
+            return None;
+        }
+        let serialized = storage::read(STORAGE_NAME)?;
+        let save: Self = ron::from_str(&serialized)
+            .inspect_err(|err| error!("Failed to parse save game: {err}"))
+            .ok()?;
+        let map_hash = registry.get(&save.level).and_then(|level| level.map_hash());
+        if map_hash.is_none() || map_hash != save.map_hash {
+            warn!(
+                "Ignoring save game, as the map of level {:?} has changed since it was saved",
+                save.level
+            );
+            return None;
+        }
+        Some(save)
+    }
+
+    fn save(&self) {
+        match ron::ser::to_string_pretty(self, default()) {
+            Ok(serialized) => storage::write(STORAGE_NAME, &serialized),
+            Err(err) => error!("Failed to serialize save game: {err}"),
+        }
+    }
+}
+
+/// A save game that will be applied as soon as the level has been spawned.
+#[derive(Debug, Resource)]
+pub(crate) struct PendingSave(pub(crate) SaveGame);
+
+/// Writes the current state of the level to storage.
+/// Use with [`Commands::run_system_cached`].
+pub(crate) fn save_game(
+    player: Single<&Transform, With<Player>>,
+    camera_parent: Single<&Transform, With<PlayerCameraParent>>,
+    q_prop: Query<
+        (&SaveKey, &Transform, &LinearVelocity, &AngularVelocity),
+        Or<(With<DynamicProp>, With<Npc>)>,
+    >,
+    q_spawned_prop: Query<
+        (&Prop, &Transform, &LinearVelocity, &AngularVelocity),
+        With<SpawnedProp>,
+    >,
+    q_door: Query<(&TargetName, &DoorState)>,
+    dialogue_runner: Single<&DialogueRunner>,
+    current_level: Res<CurrentLevel>,
+    registry: Res<LevelRegistry>,
+    visited_rooms: Res<VisitedRooms>,
+    broken_props: Res<BrokenProps>,
+) {
+    let (yaw, pitch, _roll) = camera_parent.rotation.to_euler(EulerRot::YXZ);
+    let save = SaveGame {
+        level: current_level.0.clone(),
+        map_hash: registry
+            .get(&current_level.0)
+            .and_then(|level| level.map_hash()),
+        player: PlayerSave {
+            translation: player.translation,
+            yaw,
+            pitch,
+        },
+        props: q_prop
+            .iter()
+            .map(
+                |(key, transform, linear_velocity, angular_velocity)| PropSave {
+                    key: key.0.clone(),
+                    transform: *transform,
+                    linear_velocity: linear_velocity.0,
+                    angular_velocity: angular_velocity.0,
+                },
+            )
+            .collect(),
+        variables: dialogue_runner
+            .variable_storage()
+            .variables()
+            .into_iter()
+            .map(|(name, value)| (name, value.into()))
+            .collect(),
+        visited_rooms: visited_rooms.0.iter().cloned().collect(),
+        spawned_props: q_spawned_prop
+            .iter()
+            .map(
+                |(prop, transform, linear_velocity, angular_velocity)| SpawnedPropSave {
+                    classname: prop.0.clone(),
+                    transform: *transform,
+                    linear_velocity: linear_velocity.0,
+                    angular_velocity: angular_velocity.0,
+                },
+            )
+            .collect(),
+        open_doors: q_door
+            .iter()
+            .filter(|(_, state)| state.open)
+            .map(|(name, _)| name.targetname.clone())
+            .collect(),
+        broken_props: broken_props.0.clone(),
+    };
+    save.save();
+}
+
+fn apply_pending_save(
+    mut commands: Commands,
+    pending: Res<PendingSave>,
+    mut player: Single<&mut Transform, With<Player>>,
+    mut camera_parent: Single<&mut Transform, (With<PlayerCameraParent>, Without<Player>)>,
+    mut q_prop: Query<
+        (
+            Entity,
+            &SaveKey,
+            &mut Transform,
+            &mut LinearVelocity,
+            &mut AngularVelocity,
+        ),
+        (
+            Or<(With<DynamicProp>, With<Npc>)>,
+            Without<Player>,
+            Without<PlayerCameraParent>,
+        ),
+    >,
+    mut q_door: Query<
+        (&TargetName, &Door, &mut DoorState, &mut Transform),
+        (
+            Without<Player>,
+            Without<PlayerCameraParent>,
+            Without<SaveKey>,
+        ),
+    >,
+    mut dialogue_runner: Single<&mut DialogueRunner>,
+    mut visited_rooms: ResMut<VisitedRooms>,
+    mut broken_props: ResMut<BrokenProps>,
+) {
+    let save = &pending.0;
+    player.translation = save.player.translation;
+    camera_parent.rotation =
+        Quat::from_euler(EulerRot::YXZ, save.player.yaw, save.player.pitch, 0.0);
+
+    let props: HashMap<_, _> = save
+        .props
+        .iter()
+        .map(|prop| (prop.key.as_str(), prop))
+        .collect();
+    for (entity, key, mut transform, mut linear_velocity, mut angular_velocity) in &mut q_prop {
+        if save.broken_props.contains(&key.0) {
+            commands.entity(entity).despawn_recursive();
+            continue;
+        }
+        let Some(prop) = props.get(key.0.as_str()) else {
+            warn!("Save game contains no prop or NPC {:?}", key.0);
+            continue;
+        };
+        *transform = prop.transform;
+        linear_velocity.0 = prop.linear_velocity;
+        angular_velocity.0 = prop.angular_velocity;
+    }
+
+    let variables = save
+        .variables
+        .iter()
+        .map(|(name, value)| (name.clone(), value.clone().into()))
+        .collect();
+    if let Err(err) = dialogue_runner.variable_storage_mut().extend(variables) {
+        error!("Failed to restore dialogue variables: {err}");
+    }
+
+    visited_rooms.0.extend(save.visited_rooms.iter().cloned());
+    broken_props.0.extend(save.broken_props.iter().cloned());
+
+    for prop in &save.spawned_props {
+        commands.spawn((
+            Name::new(prop.classname.clone()),
+            prop.transform,
+            Visibility::default(),
+            LinearVelocity(prop.linear_velocity),
+            AngularVelocity(prop.angular_velocity),
+            StateScoped(Screen::Gameplay),
+            SpawnedProp,
+            Prop(prop.classname.clone()),
+        ));
+    }
+
+    for (name, door, mut state, mut transform) in &mut q_door {
+        if save.open_doors.contains(&name.targetname) {
+            state.open_instantly(door, &mut transform);
+        }
+    }
+
+    commands.remove_resource::<PendingSave>();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn save_game() -> SaveGame {
+        SaveGame {
+            level: LevelId::default(),
+            map_hash: Some(42),
+            player: PlayerSave {
+                translation: Vec3::new(1.0, 2.0, 3.0),
+                yaw: 0.5,
+                pitch: -0.25,
+            },
+            props: vec![PropSave {
+                key: "crate at 0 0 16".to_string(),
+                transform: Transform::from_xyz(0.0, 1.0, 0.0),
+                linear_velocity: Vec3::X,
+                angular_velocity: Vec3::ZERO,
+            }],
+            variables: HashMap::from([
+                ("$met_follower".to_string(), SavedValue::Boolean(true)),
+                ("$coins".to_string(), SavedValue::Number(3.0)),
+                ("$name".to_string(), SavedValue::String("Fox".to_string())),
+            ]),
+            visited_rooms: vec!["kitchen".to_string()],
+            spawned_props: vec![SpawnedPropSave {
+                classname: "key".to_string(),
+                transform: Transform::default(),
+                linear_velocity: Vec3::ZERO,
+                angular_velocity: Vec3::Y,
+            }],
+            open_doors: vec!["front_door".to_string()],
+            broken_props: vec!["vase at 8 8 0".to_string()],
+        }
+    }
+
+    #[test]
+    fn save_game_survives_ron_round_trip() {
+        let serialized = ron::ser::to_string_pretty(&save_game(), default()).unwrap();
+        let deserialized: SaveGame = ron::from_str(&serialized).unwrap();
+        let serialized_again = ron::ser::to_string_pretty(&deserialized, default()).unwrap();
+        assert_eq!(serialized, serialized_again);
+    }
+
+    #[test]
+    fn old_save_games_fill_in_missing_fields() {
+        let old = r#"(
+            player: (translation: (1.0, 2.0, 3.0), yaw: 0.0, pitch: 0.0),
+            props: [],
+            variables: {},
+        )"#;
+        let save: SaveGame = ron::from_str(old).unwrap();
+        assert_eq!(save.level, LevelId::default());
+        assert_eq!(save.map_hash, None);
+        assert!(save.broken_props.is_empty());
+        assert!(save.open_doors.is_empty());
+    }
+}
@@ -0,0 +1,156 @@
+//! Yarn commands, e.g. `<<follow>>` or `<<open_door front_door>>`.
+
+use avian3d::prelude::*;
+use bevy::{ecs::system::SystemId, prelude::*};
+use bevy_yarnspinner::prelude::*;
+
+use crate::{
+    audio::SoundEffect,
+    gameplay::{
+        door::{DoorState, open_doors},
+        npc::behaviour::NpcBrain,
+        player::{
+            Player,
+            camera::{CameraTarget, PlayerCameraParent},
+        },
+        save::SpawnedProp,
+    },
+    props::{Prop, PropDefinitions},
+    screens::Screen,
+    third_party::{bevy_trenchbroom::target::TargetName, bevy_yarnspinner::InDialogue},
+};
+
+pub(super) fn plugin(app: &mut App) {
+    let commands = YarnCommands {
+        follow: app.register_system(follow),
+        stop_following: app.register_system(stop_following),
+        give_item: app.register_system(give_item),
+        play_sound: app.register_system(play_sound),
+        set_camera_target: app.register_system(set_camera_target),
+        teleport: app.register_system(teleport),
+        open_door: app.register_system(open_door),
+    };
+    app.insert_resource(commands);
+    app.add_observer(add_commands_to_dialogue_runner);
+}
+
+#[derive(Resource)]
+struct YarnCommands {
+    follow: SystemId<In<()>>,
+    stop_following: SystemId<In<()>>,
+    give_item: SystemId<In<String>>,
+    play_sound: SystemId<In<String>>,
+    set_camera_target: SystemId<In<String>>,
+    teleport: SystemId<In<String>>,
+    open_door: SystemId<In<String>>,
+}
+
+fn add_commands_to_dialogue_runner(
+    trigger: Trigger<OnAdd, DialogueRunner>,
+    mut q_dialogue_runner: Query<&mut DialogueRunner>,
+    yarn_commands: Res<YarnCommands>,
+) {
+    let mut dialogue_runner = q_dialogue_runner.get_mut(trigger.entity()).unwrap();
+    dialogue_runner
+        .commands_mut()
+        .add_command("follow", yarn_commands.follow)
+        .add_command("stop_following", yarn_commands.stop_following)
+        .add_command("give_item", yarn_commands.give_item)
+        .add_command("play_sound", yarn_commands.play_sound)
+        .add_command("set_camera_target", yarn_commands.set_camera_target)
+        .add_command("teleport", yarn_commands.teleport)
+        .add_command("open_door", yarn_commands.open_door);
+}
+
+/// `<<follow>>`: The NPC the player is talking to follows the player from now on.
+fn follow(_: In<()>, mut q_brain: Query<&mut NpcBrain, With<InDialogue>>) {
+    for mut brain in &mut q_brain {
+        brain.follows_player = true;
+    }
+}
+
+/// `<<stop_following>>`: The NPC the player is talking to stops following the player.
+fn stop_following(_: In<()>, mut q_brain: Query<&mut NpcBrain, With<InDialogue>>) {
+    for mut brain in &mut q_brain {
+        brain.follows_player = false;
+    }
+}
+
+/// `<<give_item crate>>`: Spawns a prop by its classname right in front of the player.
+fn give_item(
+    In(classname): In<String>,
+    mut commands: Commands,
+    props: Res<PropDefinitions>,
+    camera: Single<&Transform, With<PlayerCameraParent>>,
+) {
+    if !props.get(&classname).is_some_and(|prop| prop.item) {
+        error!("<<give_item>>: {classname:?} is not an item class");
+        return;
+    };
+    const DISTANCE_IN_FRONT: f32 = 1.0;
+    let translation = camera.translation + camera.forward() * DISTANCE_IN_FRONT;
+    commands.spawn((
+        Name::new(classname.clone()),
+        Transform::from_translation(translation),
+        Visibility::default(),
+        StateScoped(Screen::Gameplay),
+        SpawnedProp,
+        Prop(classname),
+    ));
+}
+
+/// `<<play_sound step/stone_01.ogg>>`: Plays a sound effect from `assets/audio/sound_effects`.
+fn play_sound(In(path): In<String>, mut commands: Commands, assets: Res<AssetServer>) {
+    commands.spawn((
+        Name::new("Dialogue Sound"),
+        AudioPlayer::<AudioSource>(assets.load(format!("audio/sound_effects/{path}"))),
+        PlaybackSettings::DESPAWN,
+        SoundEffect,
+    ));
+}
+
+/// `<<set_camera_target fireplace>>`: Turns the camera towards the entity with the given `targetname`
+/// until the dialogue ends. An empty name turns control back to the player.
+fn set_camera_target(
+    In(targetname): In<String>,
+    mut commands: Commands,
+    q_target: Query<(Entity, &TargetName)>,
+) {
+    if targetname.is_empty() {
+        commands.remove_resource::<CameraTarget>();
+        return;
+    }
+    let Some((entity, _)) = q_target
+        .iter()
+        .find(|(_, name)| name.targetname == targetname)
+    else {
+        error!("<<set_camera_target>>: No entity has the targetname {targetname:?}");
+        return;
+    };
+    commands.insert_resource(CameraTarget(entity));
+}
+
+/// `<<teleport cellar>>`: Moves the player to the entity with the given `targetname`.
+fn teleport(
+    In(targetname): In<String>,
+    q_target: Query<(&TargetName, &GlobalTransform)>,
+    player: Single<(&mut Transform, &mut LinearVelocity), With<Player>>,
+) {
+    let Some((_, target)) = q_target
+        .iter()
+        .find(|(name, _)| name.targetname == targetname)
+    else {
+        error!("<<teleport>>: No entity has the targetname {targetname:?}");
+        return;
+    };
+    let (mut transform, mut velocity) = player.into_inner();
+    transform.translation = target.translation();
+    velocity.0 = Vec3::ZERO;
+}
+
+/// `<<open_door front_door>>`: Opens all doors with the given `targetname`.
+fn open_door(In(targetname): In<String>, mut q_door: Query<(&TargetName, &mut DoorState)>) {
+    if !open_doors(&targetname, &mut q_door) {
+        error!("<<open_door>>: No door has the targetname {targetname:?}");
+    }
+}
@@ -0,0 +1,73 @@
+//! Yarn functions, e.g. `<<if player_is_holding("crate")>>`.
+//!
+//! Yarn functions cannot access the ECS, so we mirror the facts they need into
+//! [`YarnFacts`], which the functions share with the systems that keep it up to date.
+
+use std::sync::{Arc, RwLock};
+
+use avian_pickup::prop::HeldProp;
+use bevy::{prelude::*, utils::HashSet};
+use bevy_yarnspinner::prelude::*;
+
+use crate::{
+    AppSet,
+    gameplay::room::VisitedRooms,
+    props::{Prop, PropDefinitions},
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<YarnFacts>();
+    app.add_observer(add_functions_to_dialogue_runner);
+    app.add_systems(
+        Update,
+        (
+            update_held_item,
+            update_visited_rooms.run_if(resource_changed::<VisitedRooms>),
+        )
+            .in_set(AppSet::Update),
+    );
+}
+
+#[derive(Resource, Clone, Default)]
+struct YarnFacts(Arc<RwLock<Facts>>);
+
+#[derive(Debug, Default)]
+struct Facts {
+    /// The classname of the prop the player is holding.
+    held_item: Option<String>,
+    visited_rooms: HashSet<String>,
+}
+
+fn add_functions_to_dialogue_runner(
+    trigger: Trigger<OnAdd, DialogueRunner>,
+    mut q_dialogue_runner: Query<&mut DialogueRunner>,
+    facts: Res<YarnFacts>,
+) {
+    let mut dialogue_runner = q_dialogue_runner.get_mut(trigger.entity()).unwrap();
+    let held_item_facts = facts.0.clone();
+    let room_facts = facts.0.clone();
+    dialogue_runner
+        .library_mut()
+        .add_function("player_is_holding", move |classname: String| -> bool {
+            held_item_facts.read().unwrap().held_item.as_deref() == Some(classname.as_str())
+        })
+        .add_function("visited_room", move |name: String| -> bool {
+            room_facts.read().unwrap().visited_rooms.contains(&name)
+        });
+}
+
+fn update_held_item(
+    q_held_prop: Query<&Prop, With<HeldProp>>,
+    props: Res<PropDefinitions>,
+    facts: Res<YarnFacts>,
+) {
+    let held_item = q_held_prop
+        .iter()
+        .find(|prop| props.get(&prop.0).is_some_and(|definition| definition.item))
+        .map(|prop| prop.0.clone());
+    facts.0.write().unwrap().held_item = held_item;
+}
+
+fn update_visited_rooms(visited_rooms: Res<VisitedRooms>, facts: Res<YarnFacts>) {
+    facts.0.write().unwrap().visited_rooms = visited_rooms.0.clone();
+}
@@ -0,0 +1,13 @@
+//! Lets dialogue in `npc.yarn` affect the world through Yarn commands and functions.
+//!
+//! Commands act on the entity the player is talking to, the player, or entities that
+//! TrenchBroom refers to by their `targetname`.
+
+use bevy::prelude::*;
+
+mod commands;
+mod functions;
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_plugins((commands::plugin, functions::plugin));
+}
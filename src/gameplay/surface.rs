@@ -0,0 +1,170 @@
+//! Detects what characters are standing on, so that their footsteps can sound like it.
+//! Impacts of props use the same detection through [`Surfaces`].
+//!
+//! Props tell us directly through their [`PhysicsMaterial`]. For level geometry, we look at the material
+//! of the face below the character: either its `footstep` property, e.g. `footstep = "wood"` in the
+//! `[properties]` of a `*.material.toml`, or otherwise the name of its texture.
+
+use std::time::Duration;
+
+use avian3d::prelude::*;
+use bevy::{
+    ecs::system::SystemParam,
+    picking::mesh_picking::ray_cast::{MeshRayCast, RayCastSettings},
+    prelude::*,
+};
+use bevy_tnua::prelude::*;
+use bevy_trenchbroom::bevy_materialize::{GenericMaterial, prelude::*};
+
+use crate::{AppSet, screens::Screen, third_party::avian3d::PhysicsMaterial};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<GroundSurface>();
+    app.register_material_property(FOOTSTEP);
+    app.add_systems(
+        Update,
+        detect_ground_surface
+            .run_if(in_state(Screen::Gameplay))
+            .before(AppSet::PlaySounds),
+    );
+}
+
+/// The material property that decides how walking on a material sounds.
+const FOOTSTEP: MaterialProperty<String> = MaterialProperty::new("footstep", String::new);
+
+/// How often the surface below each character is detected.
+/// Footsteps are not frequent enough to notice a short delay, and casting rays against the level's meshes is not free.
+const DETECTION_INTERVAL: Duration = Duration::from_millis(200);
+
+/// What a character is currently standing on.
+#[derive(Debug, Component, Reflect, Clone, Copy, PartialEq, Eq)]
+#[reflect(Component, Debug, PartialEq)]
+pub(crate) struct GroundSurface(pub(crate) PhysicsMaterial);
+
+impl Default for GroundSurface {
+    /// Until we know better, assume the surface our footstep recordings were made on.
+    fn default() -> Self {
+        Self(PhysicsMaterial::Stone)
+    }
+}
+
+impl PhysicsMaterial {
+    /// Guesses the material from a texture or material name, e.g. `rock_wall_13_1k` or `wood`.
+    fn from_name(name: &str) -> Option<Self> {
+        let name = name.to_lowercase();
+        let matches_any = |words: &[&str]| words.iter().any(|word| name.contains(word));
+        if matches_any(&["wood", "plank", "timber"]) {
+            Some(Self::Wood)
+        } else if matches_any(&["metal", "iron", "steel", "grate"]) {
+            Some(Self::Metal)
+        } else if matches_any(&["rock", "stone", "brick", "concrete"]) {
+            Some(Self::Stone)
+        } else {
+            None
+        }
+    }
+}
+
+/// Finds out what something is made of, be it a prop or level geometry.
+#[derive(SystemParam)]
+pub(crate) struct Surfaces<'w, 's> {
+    q_collider_parent: Query<'w, 's, &'static ColliderParent>,
+    q_body: Query<'w, 's, &'static RigidBody>,
+    q_material: Query<'w, 's, &'static PhysicsMaterial>,
+    q_level_mesh: Query<'w, 's, &'static GenericMaterial3d>,
+    generic_materials: Res<'w, Assets<GenericMaterial>>,
+    mesh_ray_cast: MeshRayCast<'w, 's>,
+}
+
+impl Surfaces<'_, '_> {
+    /// The material of the body that `collider` belongs to.
+    /// Level geometry consists of one collider for all of its faces,
+    /// so for it we look at the face that `ray` hits first instead.
+    pub(crate) fn material_of(&mut self, collider: Entity, ray: Ray3d) -> Option<PhysicsMaterial> {
+        let body = self
+            .q_collider_parent
+            .get(collider)
+            .map_or(collider, ColliderParent::get);
+        if let Ok(material) = self.q_material.get(body).or(self.q_material.get(collider)) {
+            return Some(*material);
+        }
+        // Characters and other dynamic bodies are not level geometry.
+        if self.q_body.get(body).is_ok_and(|body| !body.is_static()) {
+            return None;
+        }
+
+        let filter = |entity: Entity| self.q_level_mesh.contains(entity);
+        let settings = RayCastSettings::default().with_filter(&filter);
+        let (mesh, _hit) = self.mesh_ray_cast.cast_ray(ray, &settings).first()?;
+        let material = self.q_level_mesh.get(*mesh).ok()?;
+        surface_of(material, &self.generic_materials)
+    }
+}
+
+fn detect_ground_surface(
+    mut q_character: Query<(&TnuaProximitySensor, &GlobalTransform, &mut GroundSurface)>,
+    mut surfaces: Surfaces,
+    time: Res<Time>,
+    mut timer: Local<Option<Timer>>,
+) {
+    let timer = timer.get_or_insert_with(|| Timer::new(DETECTION_INTERVAL, TimerMode::Repeating));
+    if !timer.tick(time.delta()).just_finished() {
+        return;
+    }
+
+    for (sensor, transform, mut surface) in &mut q_character {
+        let Some(ground) = sensor.output.as_ref() else {
+            continue;
+        };
+        let ray = Ray3d::new(transform.translation(), Dir3::NEG_Y);
+        if let Some(material) = surfaces.material_of(ground.entity, ray) {
+            surface.set_if_neq(GroundSurface(material));
+        }
+    }
+}
+
+fn surface_of(
+    material: &GenericMaterial3d,
+    generic_materials: &Assets<GenericMaterial>,
+) -> Option<PhysicsMaterial> {
+    let from_property = generic_materials
+        .get(&material.0)
+        .and_then(|generic_material| generic_material.get_property(FOOTSTEP).ok())
+        .and_then(|footstep| PhysicsMaterial::from_name(footstep.as_str()));
+    let from_name = || {
+        let path = material.0.path()?;
+        let name = path.path().file_stem()?.to_str()?;
+        PhysicsMaterial::from_name(name)
+    };
+    from_property.or_else(from_name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn material_is_guessed_from_name() {
+        assert_eq!(
+            PhysicsMaterial::from_name("wood"),
+            Some(PhysicsMaterial::Wood)
+        );
+        assert_eq!(
+            PhysicsMaterial::from_name("Old_Planks"),
+            Some(PhysicsMaterial::Wood)
+        );
+        assert_eq!(
+            PhysicsMaterial::from_name("floor_grate"),
+            Some(PhysicsMaterial::Metal)
+        );
+        assert_eq!(
+            PhysicsMaterial::from_name("rock_wall_13_1k"),
+            Some(PhysicsMaterial::Stone)
+        );
+        assert_eq!(
+            PhysicsMaterial::from_name("bricks"),
+            Some(PhysicsMaterial::Stone)
+        );
+        assert_eq!(PhysicsMaterial::from_name("skip"), None);
+    }
+}
@@ -3,20 +3,18 @@ pub(crate) mod audio;
 #[cfg(feature = "dev")]
 mod dev_tools;
 mod gameplay;
+mod menus;
 mod props;
 mod screens;
+mod settings;
+mod storage;
 mod theme;
 mod third_party;
 mod ui_camera;
 
 use bitflags::bitflags;
 
-use bevy::{
-    asset::AssetMetaCheck,
-    audio::{AudioPlugin, Volume},
-    prelude::*,
-    render::view::RenderLayers,
-};
+use bevy::{asset::AssetMetaCheck, prelude::*, render::view::RenderLayers};
 
 pub struct AppPlugin;
 
@@ -53,22 +51,19 @@ impl Plugin for AppPlugin {
                     }
                     .into(),
                     ..default()
-                })
-                .set(AudioPlugin {
-                    global_volume: GlobalVolume {
-                        volume: Volume::new(0.3),
-                    },
-                    ..default()
                 }),
         );
 
         // Add other plugins.
         app.add_plugins((
+            settings::plugin,
+            audio::plugin,
             third_party::plugin,
             ui_camera::plugin,
             asset_tracking::plugin,
             gameplay::plugin,
             screens::plugin,
+            menus::plugin,
             theme::plugin,
             props::plugin,
         ));
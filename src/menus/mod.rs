@@ -0,0 +1,127 @@
+//! In-game menus that are layered over [`Screen::Gameplay`].
+//! While a menu is open, physics is paused and the player cannot be controlled.
+
+mod pause;
+mod settings;
+
+use std::any::Any as _;
+
+use avian3d::prelude::*;
+use bevy::{input::common_conditions::input_just_pressed, prelude::*};
+use bevy_enhanced_input::prelude::*;
+
+use crate::{
+    AppSet,
+    gameplay::{
+        crosshair::{CrosshairState, cursor::release_cursor},
+        player::{
+            Player, default_input::block_default_inputs, dialogue_input::block_dialogue_inputs,
+        },
+    },
+    screens::Screen,
+    settings::Settings,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_sub_state::<Menu>();
+    app.enable_state_scoped_entities::<Menu>();
+    app.add_input_context::<MenuInputContext>();
+    app.add_observer(menu_binding);
+
+    app.add_systems(
+        OnEnter(Menu::Pause),
+        (pause_game, release_cursor.param_warn_once()),
+    );
+    app.add_systems(OnEnter(Menu::None), resume_game);
+    app.add_systems(OnExit(Screen::Gameplay), unpause_physics);
+    app.add_systems(
+        Update,
+        toggle_pause_menu
+            .run_if(in_state(Screen::Gameplay).and(input_just_pressed(KeyCode::Escape)))
+            .in_set(AppSet::Update),
+    );
+
+    app.add_plugins((pause::plugin, settings::plugin));
+}
+
+/// The menu that is currently open on top of the gameplay.
+#[derive(SubStates, Debug, Hash, PartialEq, Eq, Clone, Copy, Default)]
+#[source(Screen = Screen::Gameplay)]
+pub(crate) enum Menu {
+    #[default]
+    None,
+    Pause,
+    Settings,
+}
+
+/// Run condition that is true while any in-game menu is open.
+pub(crate) fn is_menu_open(menu: Option<Res<State<Menu>>>) -> bool {
+    menu.is_some_and(|menu| *menu.get() != Menu::None)
+}
+
+/// Added to the player while a menu is open.
+/// Its priority is higher than that of every gameplay context, so it can consume all of their inputs
+/// without them losing the state of their bindings.
+#[derive(Debug, InputContext, Default)]
+#[input_context(priority = 2)]
+struct MenuInputContext;
+
+fn menu_binding(
+    trigger: Trigger<Binding<MenuInputContext>>,
+    mut q_actions: Query<&mut Actions<MenuInputContext>>,
+    settings: Res<Settings>,
+) {
+    let mut actions = q_actions.get_mut(trigger.entity()).unwrap();
+    // The menus themselves are navigated through the UI.
+    block_default_inputs(&mut actions, &settings.keybinds);
+    block_dialogue_inputs(&mut actions);
+}
+
+fn toggle_pause_menu(menu: Res<State<Menu>>, mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(match menu.get() {
+        Menu::None => Menu::Pause,
+        Menu::Pause => Menu::None,
+        Menu::Settings => Menu::Pause,
+    });
+}
+
+fn pause_game(
+    mut commands: Commands,
+    mut physics_time: ResMut<Time<Physics>>,
+    mut q_crosshair: Query<&mut CrosshairState>,
+    q_player: Query<Entity, With<Player>>,
+) {
+    // The Tnua controller does not run while the physics time is paused,
+    // so this also freezes the character controllers.
+    physics_time.pause();
+    for mut crosshair in &mut q_crosshair {
+        crosshair.wants_invisible.insert(pause_game.type_id());
+    }
+    // Blocks movement, pickup and dialogue interaction.
+    for player in &q_player {
+        commands
+            .entity(player)
+            .insert(Actions::<MenuInputContext>::default());
+    }
+}
+
+fn resume_game(
+    mut commands: Commands,
+    mut physics_time: ResMut<Time<Physics>>,
+    mut q_crosshair: Query<&mut CrosshairState>,
+    q_player: Query<Entity, With<Player>>,
+) {
+    physics_time.unpause();
+    for mut crosshair in &mut q_crosshair {
+        crosshair.wants_invisible.remove(&pause_game.type_id());
+    }
+    for player in &q_player {
+        commands
+            .entity(player)
+            .remove::<Actions<MenuInputContext>>();
+    }
+}
+
+fn unpause_physics(mut physics_time: ResMut<Time<Physics>>) {
+    physics_time.unpause();
+}
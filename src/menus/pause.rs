@@ -0,0 +1,54 @@
+//! The pause menu that opens when pressing Escape during gameplay.
+
+use bevy::prelude::*;
+
+use crate::{
+    gameplay::save::save_game,
+    screens::Screen,
+    theme::{interaction::OnPress, palette::OVERLAY_BACKGROUND, prelude::*},
+};
+
+use super::Menu;
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(OnEnter(Menu::Pause), spawn_pause_menu);
+}
+
+fn spawn_pause_menu(mut commands: Commands) {
+    commands
+        .ui_root()
+        .insert((
+            Name::new("Pause Menu"),
+            BackgroundColor(OVERLAY_BACKGROUND),
+            StateScoped(Menu::Pause),
+        ))
+        .with_children(|children| {
+            children.header("Paused");
+            children.button("Resume").observe(resume);
+            children.button("Save").observe(save);
+            children.button("Settings").observe(open_settings_menu);
+            children.button("Quit to title").observe(quit_to_title);
+        });
+}
+
+fn resume(_trigger: Trigger<OnPress>, mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(Menu::None);
+}
+
+fn save(_trigger: Trigger<OnPress>, mut commands: Commands) {
+    commands.run_system_cached(save_game);
+}
+
+fn open_settings_menu(_trigger: Trigger<OnPress>, mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(Menu::Settings);
+}
+
+/// Saves automatically so that the game can be continued from the title screen.
+fn quit_to_title(
+    _trigger: Trigger<OnPress>,
+    mut commands: Commands,
+    mut next_screen: ResMut<NextState<Screen>>,
+) {
+    commands.run_system_cached(save_game);
+    next_screen.set(Screen::Title);
+}
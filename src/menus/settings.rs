@@ -0,0 +1,33 @@
+//! The settings menu that can be opened from the pause menu.
+
+use bevy::prelude::*;
+
+use crate::{
+    screens::settings::spawn_settings_widgets,
+    settings::Settings,
+    theme::{interaction::OnPress, palette::OVERLAY_BACKGROUND, prelude::*},
+};
+
+use super::Menu;
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(OnEnter(Menu::Settings), spawn_settings_menu);
+}
+
+fn spawn_settings_menu(mut commands: Commands, settings: Res<Settings>) {
+    commands
+        .ui_root()
+        .insert((
+            Name::new("Settings Menu"),
+            BackgroundColor(OVERLAY_BACKGROUND),
+            StateScoped(Menu::Settings),
+        ))
+        .with_children(|parent| {
+            spawn_settings_widgets(parent, &settings);
+            parent.button("Back").observe(return_to_pause_menu);
+        });
+}
+
+fn return_to_pause_menu(_trigger: Trigger<OnPress>, mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(Menu::Pause);
+}
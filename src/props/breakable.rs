@@ -0,0 +1,272 @@
+//! Props that break into debris once they have taken enough damage from impacts,
+//! e.g. a thrown crate hitting a wall.
+
+use std::time::Duration;
+
+use avian3d::prelude::*;
+use bevy::prelude::*;
+use bevy_trenchbroom::prelude::*;
+use rand::Rng as _;
+
+#[cfg(feature = "native")]
+use super::effects::{ParticleEffects, splinter_burst_bundle};
+use super::{
+    Prop,
+    definition::{BreakableDefinition, PropDefinitions},
+};
+use crate::{
+    AppSet,
+    audio::SoundEffect,
+    screens::Screen,
+    third_party::avian3d::{CollisionLayer, PhysicsMaterial},
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<(Breakable, Expires)>();
+    app.add_systems(
+        Update,
+        (
+            despawn_expired.in_set(AppSet::TickTimers),
+            (damage_breakables, break_props)
+                .chain()
+                .in_set(AppSet::Update),
+        )
+            .run_if(in_state(Screen::Gameplay)),
+    );
+}
+
+/// A prop that breaks once its health runs out.
+#[derive(BaseClass, Component, Debug, Clone, Copy, PartialEq, Default, Reflect)]
+#[reflect(Component, Default, Debug)]
+pub(crate) struct Breakable {
+    /// How much damage the prop can take before breaking.
+    /// Zero uses the health from props.ron, a negative value makes the prop unbreakable.
+    pub(crate) health: f32,
+}
+
+/// Triggered on a prop right before it is replaced by debris.
+#[derive(Event, Debug)]
+pub(crate) struct PropBroken;
+
+/// Impacts below this impulse (in N*s) don't do any damage,
+/// so that props resting on the floor or being pushed around don't break.
+const IMPULSE_WITHOUT_DAMAGE: f32 = 150.0;
+/// How much damage each N*s of impulse above [`IMPULSE_WITHOUT_DAMAGE`] does.
+const DAMAGE_PER_IMPULSE: f32 = 0.1;
+/// How fast debris flies away from the broken prop.
+const DEBRIS_SPEED: f32 = 3.0;
+const DEBRIS_LIFETIME: Duration = Duration::from_secs(10);
+#[cfg(feature = "native")]
+const BURST_LIFETIME: Duration = Duration::from_secs(2);
+
+/// Decides the health of a freshly spawned prop.
+/// Breakable props spawned from a map already have a [`Breakable`] holding the health set in TrenchBroom.
+pub(super) fn resolve_health(
+    definition: Option<BreakableDefinition>,
+) -> impl FnOnce(EntityWorldMut) {
+    move |mut entity| {
+        let health = entity
+            .get::<Breakable>()
+            .map(|breakable| breakable.health)
+            .filter(|health| *health != 0.0)
+            .or(definition.map(|definition| definition.health));
+        match health {
+            Some(health) if health > 0.0 => {
+                entity.insert(Breakable { health });
+            }
+            _ => {
+                entity.remove::<Breakable>();
+            }
+        }
+    }
+}
+
+/// Despawns the entity once the timer finishes.
+#[derive(Component, Debug, Clone, Reflect)]
+#[reflect(Component)]
+struct Expires(Timer);
+
+impl Expires {
+    fn after(duration: Duration) -> Self {
+        Self(Timer::new(duration, TimerMode::Once))
+    }
+}
+
+fn despawn_expired(
+    mut commands: Commands,
+    mut q_expires: Query<(Entity, &mut Expires)>,
+    time: Res<Time>,
+) {
+    for (entity, mut expires) in &mut q_expires {
+        if expires.0.tick(time.delta()).just_finished() {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
+
+fn damage_breakables(
+    mut collisions: EventReader<Collision>,
+    mut q_breakable: Query<&mut Breakable>,
+) {
+    for Collision(contacts) in collisions.read() {
+        let damage =
+            (contacts.total_normal_impulse - IMPULSE_WITHOUT_DAMAGE).max(0.0) * DAMAGE_PER_IMPULSE;
+        if damage == 0.0 {
+            continue;
+        }
+        // The colliders of props are children of their rigid body.
+        let bodies = [
+            contacts.body_entity1.unwrap_or(contacts.entity1),
+            contacts.body_entity2.unwrap_or(contacts.entity2),
+        ];
+        for body in bodies {
+            if let Ok(mut breakable) = q_breakable.get_mut(body) {
+                breakable.health -= damage;
+            }
+        }
+    }
+}
+
+fn break_props(
+    mut commands: Commands,
+    q_breakable: Query<(
+        Entity,
+        &Breakable,
+        &Prop,
+        &GlobalTransform,
+        Option<&LinearVelocity>,
+        Option<&PhysicsMaterial>,
+    )>,
+    definitions: Res<PropDefinitions>,
+    assets: Res<AssetServer>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    #[cfg(feature = "native")] mut particle_effects: ResMut<ParticleEffects>,
+    #[cfg(feature = "native")] mut effects: ResMut<Assets<bevy_hanabi::EffectAsset>>,
+) {
+    let rng = &mut rand::thread_rng();
+    for (entity, breakable, prop, transform, velocity, material) in &q_breakable {
+        if breakable.health > 0.0 {
+            continue;
+        }
+        commands.trigger_targets(PropBroken, entity);
+        commands.entity(entity).despawn_recursive();
+
+        let definition = definitions
+            .get(&prop.0)
+            .and_then(|definition| definition.breakable.clone())
+            .unwrap_or_default();
+        let translation = transform.translation();
+        let velocity = velocity.map_or(Vec3::ZERO, |velocity| velocity.0);
+        let material = material.copied().unwrap_or_default();
+
+        let size = definition.debris_size;
+        let (red, green, blue) = definition.debris_color;
+        let debris_material = materials.add(Color::srgb(red, green, blue));
+        for _ in 0..definition.debris_count {
+            let extents = Vec3::new(
+                size * rng.gen_range(0.5..1.5),
+                size * rng.gen_range(0.5..1.5),
+                size * rng.gen_range(0.5..1.5),
+            );
+            let direction = Vec3::new(
+                rng.gen_range(-1.0..1.0),
+                rng.gen_range(0.0..1.0),
+                rng.gen_range(-1.0..1.0),
+            )
+            .normalize_or(Vec3::Y);
+            commands.spawn((
+                Name::new("Debris"),
+                Transform::from_translation(translation + direction * size),
+                Mesh3d(meshes.add(Cuboid::from_size(extents))),
+                MeshMaterial3d(debris_material.clone()),
+                material,
+                RigidBody::Dynamic,
+                Collider::cuboid(extents.x, extents.y, extents.z),
+                CollisionLayers::new(CollisionLayer::Prop, LayerMask::ALL),
+                TransformInterpolation,
+                LinearVelocity(velocity + direction * DEBRIS_SPEED * rng.gen_range(0.5..1.0)),
+                Expires::after(DEBRIS_LIFETIME),
+                StateScoped(Screen::Gameplay),
+            ));
+        }
+
+        if let Some(sound) = &definition.sound {
+            commands.spawn((
+                Name::new("Break Sound"),
+                Transform::from_translation(translation),
+                AudioPlayer::<AudioSource>(assets.load(sound)),
+                PlaybackSettings::DESPAWN.with_spatial(true),
+                SoundEffect,
+            ));
+        }
+
+        #[cfg(feature = "native")]
+        commands.spawn((
+            Name::new("Break Particles"),
+            Transform::from_translation(translation),
+            splinter_burst_bundle(
+                &mut particle_effects,
+                &mut effects,
+                Color::srgb(red, green, blue),
+            ),
+            Expires::after(BURST_LIFETIME),
+            StateScoped(Screen::Gameplay),
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn definition(health: f32) -> Option<BreakableDefinition> {
+        Some(BreakableDefinition {
+            health,
+            debris_count: 0,
+            debris_size: 0.0,
+            debris_color: (0.0, 0.0, 0.0),
+            sound: None,
+        })
+    }
+
+    /// Spawns a prop with the given components and resolves its health.
+    fn resolved_health(
+        components: impl Bundle,
+        definition: Option<BreakableDefinition>,
+    ) -> Option<f32> {
+        let mut world = World::new();
+        let entity = world.spawn(components).id();
+        resolve_health(definition)(world.entity_mut(entity));
+        world
+            .get::<Breakable>(entity)
+            .map(|breakable| breakable.health)
+    }
+
+    #[test]
+    fn health_from_map_overrides_definition() {
+        let health = resolved_health(Breakable { health: 50.0 }, definition(100.0));
+        assert_eq!(health, Some(50.0));
+    }
+
+    #[test]
+    fn zero_health_uses_definition() {
+        let health = resolved_health(Breakable { health: 0.0 }, definition(100.0));
+        assert_eq!(health, Some(100.0));
+        // E.g. a prop spawned by dialogue instead of from the map.
+        let health = resolved_health((), definition(100.0));
+        assert_eq!(health, Some(100.0));
+    }
+
+    #[test]
+    fn negative_health_is_unbreakable() {
+        let health = resolved_health(Breakable { health: -1.0 }, definition(100.0));
+        assert_eq!(health, None);
+    }
+
+    #[test]
+    fn props_without_definition_are_unbreakable() {
+        assert_eq!(resolved_health((), None), None);
+        assert_eq!(resolved_health(Breakable { health: 0.0 }, None), None);
+    }
+}
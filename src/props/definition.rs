@@ -0,0 +1,266 @@
+//! The data-driven description of every prop, read from `assets/props.ron`.
+//!
+//! The TrenchBroom classes of the props are generated from these definitions when the game starts,
+//! so adding or tweaking a prop only requires editing the file and restarting the game.
+
+use bevy::{prelude::*, utils::HashMap};
+use serde::Deserialize;
+
+use crate::third_party::{avian3d::PhysicsMaterial, bevy_trenchbroom::preload::ClassPreloads};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<PropDefinitions>();
+    app.add_systems(Startup, register_preloads);
+}
+
+/// The path of the prop definitions, relative to the assets directory.
+const PROPS_PATH: &str = "props.ron";
+
+/// All props that can be placed in TrenchBroom, by classname.
+#[derive(Resource, Debug, Default)]
+pub(crate) struct PropDefinitions(HashMap<String, PropDefinition>);
+
+impl PropDefinitions {
+    pub(crate) fn get(&self, classname: &str) -> Option<&PropDefinition> {
+        self.0.get(classname)
+    }
+
+    pub(crate) fn iter(&self) -> impl Iterator<Item = (&str, &PropDefinition)> {
+        self.0
+            .iter()
+            .map(|(classname, definition)| (classname.as_str(), definition))
+    }
+
+    /// Reads the definitions synchronously, as the TrenchBroom classes need to exist
+    /// before the asset server is up and running.
+    fn read() -> Self {
+        let definitions = match read_props_file() {
+            Ok(contents) => {
+                ron::from_str::<Vec<PropDefinition>>(&contents).map_err(|err| err.to_string())
+            }
+            Err(err) => Err(err),
+        };
+        match definitions {
+            Ok(definitions) => Self(
+                definitions
+                    .into_iter()
+                    .map(|definition| (definition.classname.clone(), definition))
+                    .collect(),
+            ),
+            Err(err) => {
+                error!("Failed to read prop definitions from {PROPS_PATH}: {err}");
+                Self::default()
+            }
+        }
+    }
+}
+
+impl FromWorld for PropDefinitions {
+    fn from_world(_world: &mut World) -> Self {
+        Self::read()
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn read_props_file() -> Result<String, String> {
+    let path = bevy::asset::io::file::FileAssetReader::get_base_path()
+        .join("assets")
+        .join(PROPS_PATH);
+    std::fs::read_to_string(&path).map_err(|err| format!("{}: {err}", path.display()))
+}
+
+/// There is no file system on the web, so web builds bake the definitions in.
+#[cfg(target_arch = "wasm32")]
+fn read_props_file() -> Result<String, String> {
+    Ok(include_str!("../../assets/props.ron").to_string())
+}
+
+/// Everything needed to generate the TrenchBroom class of a prop and to spawn it.
+#[derive(Debug, Clone, Deserialize)]
+pub(crate) struct PropDefinition {
+    pub(crate) classname: String,
+    /// The path to the glTF model, relative to the assets directory.
+    pub(crate) model: String,
+    pub(crate) body: PropBody,
+    pub(crate) collider: PropCollider,
+    /// Decides the sounds the prop makes when it hits something.
+    #[serde(default)]
+    pub(crate) material: PhysicsMaterial,
+    /// In kg/m^3. Defaults to about the density of oak wood for dynamic props.
+    #[serde(default)]
+    pub(crate) density: Option<f32>,
+    /// Whether the model casts and receives shadows.
+    /// Turn this off for props with light sources inside of them.
+    #[serde(default = "yes")]
+    pub(crate) shadows: bool,
+    /// Whether dialogue can give this prop to the player or check if the player is holding it.
+    #[serde(default)]
+    pub(crate) item: bool,
+    #[serde(default)]
+    pub(crate) lights: Vec<PropLight>,
+    #[serde(default)]
+    pub(crate) sounds: Vec<PropSound>,
+    #[serde(default)]
+    pub(crate) particles: Vec<PropParticles>,
+    /// Makes the prop break into debris after taking enough damage from impacts.
+    #[serde(default)]
+    pub(crate) breakable: Option<BreakableDefinition>,
+}
+
+fn yes() -> bool {
+    true
+}
+
+impl PropDefinition {
+    pub(crate) fn scene_path(&self) -> String {
+        format!("{}#Scene0", self.model)
+    }
+
+    /// Starts loading all assets the prop needs.
+    fn preload(&self, assets: &AssetServer) -> Vec<UntypedHandle> {
+        let model = assets.load::<Scene>(self.scene_path()).untyped();
+        let sounds = self
+            .sounds
+            .iter()
+            .map(|sound| assets.load::<AudioSource>(&sound.path).untyped());
+        let textures = self
+            .particles
+            .iter()
+            .map(|particles| assets.load::<Image>(&particles.texture).untyped());
+        let break_sound = self
+            .breakable
+            .iter()
+            .filter_map(|breakable| breakable.sound.as_ref())
+            .map(|sound| assets.load::<AudioSource>(sound).untyped());
+        std::iter::once(model)
+            .chain(sounds)
+            .chain(textures)
+            .chain(break_sound)
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub(crate) enum PropBody {
+    /// Never moves, e.g. furniture that is too heavy to be picked up.
+    Static,
+    /// Can be pushed around and picked up by the player.
+    Dynamic,
+}
+
+/// How the collider is generated from the model.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub(crate) enum PropCollider {
+    /// Cheap, but fills in holes and concave parts.
+    Hull,
+    /// Follows the shape of the model closely, e.g. for tables and chairs.
+    Decomposition,
+}
+
+/// A point light attached to a prop.
+#[derive(Debug, Clone, Deserialize)]
+pub(crate) struct PropLight {
+    /// Relative to the prop's origin.
+    #[serde(default)]
+    pub(crate) offset: Vec3,
+    /// In sRGB.
+    pub(crate) color: (f32, f32, f32),
+    pub(crate) intensity: f32,
+    pub(crate) radius: f32,
+    #[serde(default = "yes")]
+    pub(crate) shadows: bool,
+    /// Whether the intensity flickers like a fire.
+    #[serde(default)]
+    pub(crate) flicker: bool,
+}
+
+/// A looping spatial sound attached to a prop.
+#[derive(Debug, Clone, Deserialize)]
+pub(crate) struct PropSound {
+    /// Relative to the assets directory.
+    pub(crate) path: String,
+    #[serde(default = "full_volume")]
+    pub(crate) volume: f32,
+    /// How quickly the sound gets quieter with distance.
+    #[serde(default = "full_volume")]
+    pub(crate) spatial_scale: f32,
+}
+
+fn full_volume() -> f32 {
+    1.0
+}
+
+/// A particle effect attached to a prop.
+#[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(
+    not(feature = "native"),
+    expect(dead_code, reason = "Particles are only supported in native builds")
+)]
+pub(crate) struct PropParticles {
+    pub(crate) effect: ParticleKind,
+    /// The texture of a single particle, relative to the assets directory.
+    pub(crate) texture: String,
+    /// Relative to the prop's origin.
+    #[serde(default)]
+    pub(crate) offset: Vec3,
+}
+
+/// How a prop breaks.
+#[derive(Debug, Clone, Deserialize)]
+pub(crate) struct BreakableDefinition {
+    /// Can be overridden per entity in TrenchBroom.
+    pub(crate) health: f32,
+    #[serde(default = "default_debris_count")]
+    pub(crate) debris_count: u32,
+    /// The edge length of a debris piece in meters.
+    #[serde(default = "default_debris_size")]
+    pub(crate) debris_size: f32,
+    /// In sRGB.
+    #[serde(default = "default_debris_color")]
+    pub(crate) debris_color: (f32, f32, f32),
+    /// Played once when the prop breaks, relative to the assets directory.
+    #[serde(default)]
+    pub(crate) sound: Option<String>,
+}
+
+fn default_debris_count() -> u32 {
+    6
+}
+
+fn default_debris_size() -> f32 {
+    0.15
+}
+
+fn default_debris_color() -> (f32, f32, f32) {
+    (0.5, 0.5, 0.5)
+}
+
+/// Used when the definition of a broken prop cannot be found.
+impl Default for BreakableDefinition {
+    fn default() -> Self {
+        Self {
+            health: 0.0,
+            debris_count: default_debris_count(),
+            debris_size: default_debris_size(),
+            debris_color: default_debris_color(),
+            sound: None,
+        }
+    }
+}
+
+/// The particle effects that are available to props.
+/// They are built in code, so adding a new kind means adding a variant here.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+pub(crate) enum ParticleKind {
+    Fire,
+}
+
+/// Levels preload the assets of every prop that appears in their map.
+fn register_preloads(definitions: Res<PropDefinitions>, mut preloads: ResMut<ClassPreloads>) {
+    for (classname, definition) in definitions.iter() {
+        let definition = definition.clone();
+        preloads.insert(classname.to_string(), move |_entity, assets| {
+            definition.preload(assets)
+        });
+    }
+}
@@ -1,10 +1,37 @@
+#[cfg(feature = "native")]
+use std::f32::consts::TAU;
+
 use bevy::{
+    audio::{SpatialScale, Volume},
+    ecs::world::DeferredWorld,
     pbr::{NotShadowCaster, NotShadowReceiver},
     prelude::*,
     scene::SceneInstanceReady,
 };
+#[cfg(feature = "native")]
+use bevy::{render::view::RenderLayers, utils::HashMap};
+#[cfg(feature = "native")]
+use bevy_hanabi::prelude::*;
+
+use crate::{AppSet, audio::SoundEffect, screens::Screen};
 
-pub(super) fn plugin(_app: &mut App) {}
+#[cfg(feature = "native")]
+use super::definition::{ParticleKind, PropParticles};
+use super::definition::{PropLight, PropSound};
+#[cfg(feature = "native")]
+use crate::RenderLayer;
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<Flicker>();
+    #[cfg(feature = "native")]
+    app.init_resource::<ParticleEffects>();
+    app.add_systems(
+        Update,
+        flicker_light
+            .run_if(in_state(Screen::Gameplay))
+            .in_set(AppSet::Update),
+    );
+}
 
 pub(crate) fn insert_not_shadow_caster(
     trigger: Trigger<SceneInstanceReady>,
@@ -21,3 +48,271 @@ pub(crate) fn insert_not_shadow_caster(
             .insert((NotShadowCaster, NotShadowReceiver));
     }
 }
+
+pub(super) fn light_bundle(light: &PropLight) -> impl Bundle {
+    let (red, green, blue) = light.color;
+    (
+        Name::new("Prop Light"),
+        Transform::from_translation(light.offset),
+        PointLight {
+            color: Color::srgb(red, green, blue),
+            intensity: light.intensity,
+            radius: light.radius,
+            shadows_enabled: light.shadows,
+            ..default()
+        },
+    )
+}
+
+pub(super) fn sound_bundle(world: &DeferredWorld, sound: &PropSound) -> impl Bundle {
+    let source: Handle<AudioSource> = world.resource::<AssetServer>().load(&sound.path);
+    (
+        Name::new("Prop Sound"),
+        Transform::default(),
+        AudioPlayer(source),
+        PlaybackSettings::LOOP
+            .with_spatial(true)
+            .with_volume(Volume::new(sound.volume))
+            .with_spatial_scale(SpatialScale::new(sound.spatial_scale)),
+        SoundEffect,
+    )
+}
+
+#[derive(Debug, Component, Reflect)]
+#[reflect(Component)]
+pub(super) struct Flicker {
+    pub(super) base_intensity: f32,
+}
+
+fn flicker_light(time: Res<Time>, mut query: Query<(&mut PointLight, &Flicker)>) {
+    for (mut light, flicker) in &mut query {
+        let flickers_per_second = 20.0;
+        let flicker_percentage = 0.1;
+        let wave = (time.elapsed_secs() * flickers_per_second).sin();
+        light.intensity = flicker.base_intensity * (1.0 + wave * flicker_percentage);
+    }
+}
+
+/// The particle effects shared by all props, so that each prop doesn't build its own copy.
+#[cfg(feature = "native")]
+#[derive(Resource, Debug)]
+pub(super) struct ParticleEffects {
+    fire: Handle<EffectAsset>,
+    /// Built on demand, by the sRGB color of the splinters.
+    splinters: HashMap<[u8; 4], Handle<EffectAsset>>,
+}
+
+#[cfg(feature = "native")]
+impl FromWorld for ParticleEffects {
+    fn from_world(world: &mut World) -> Self {
+        let mut effects = world.resource_mut::<Assets<EffectAsset>>();
+        Self {
+            fire: setup_fire_particles(&mut effects),
+            splinters: default(),
+        }
+    }
+}
+
+#[cfg(feature = "native")]
+impl ParticleEffects {
+    fn of_kind(&self, kind: ParticleKind) -> Handle<EffectAsset> {
+        match kind {
+            ParticleKind::Fire => self.fire.clone(),
+        }
+    }
+
+    fn splinters(
+        &mut self,
+        effects: &mut Assets<EffectAsset>,
+        color: Color,
+    ) -> Handle<EffectAsset> {
+        self.splinters
+            .entry(color.to_srgba().to_u8_array())
+            .or_insert_with(|| setup_splinter_particles(effects, color))
+            .clone()
+    }
+}
+
+#[cfg(feature = "native")]
+pub(super) fn particle_bundle(world: &DeferredWorld, particles: &PropParticles) -> impl Bundle {
+    let effect_handle = world
+        .resource::<ParticleEffects>()
+        .of_kind(particles.effect);
+    let texture: Handle<Image> = world.resource::<AssetServer>().load(&particles.texture);
+    (
+        Name::new("Prop Particles"),
+        Transform::from_translation(particles.offset),
+        ParticleEffect::new(effect_handle),
+        RenderLayers::from(RenderLayer::PARTICLES),
+        EffectMaterial {
+            images: vec![texture],
+        },
+    )
+}
+
+/// A one-shot burst of splinters, e.g. when a prop breaks.
+#[cfg(feature = "native")]
+pub(super) fn splinter_burst_bundle(
+    particle_effects: &mut ParticleEffects,
+    effects: &mut Assets<EffectAsset>,
+    color: Color,
+) -> impl Bundle {
+    (
+        ParticleEffect::new(particle_effects.splinters(effects, color)),
+        RenderLayers::from(RenderLayer::PARTICLES),
+    )
+}
+
+#[cfg(feature = "native")]
+fn setup_splinter_particles(
+    effects: &mut Assets<EffectAsset>,
+    color: Color,
+) -> Handle<EffectAsset> {
+    let writer = ExprWriter::new();
+
+    // Fly outwards in all directions
+    let velocity = SetVelocitySphereModifier {
+        center: writer.lit(Vec3::ZERO).expr(),
+        speed: writer.lit(2.0).uniform(writer.lit(5.0)).expr(),
+    };
+
+    let orientation = OrientModifier {
+        rotation: Some(writer.lit(0.0).uniform(writer.lit(TAU)).expr()),
+        mode: OrientMode::FaceCameraPosition,
+    };
+
+    let lifetime = SetAttributeModifier::new(
+        Attribute::LIFETIME,
+        writer.lit(0.5).uniform(writer.lit(1.0)).expr(),
+    );
+
+    let mut module = writer.finish();
+
+    let init_pos = SetPositionSphereModifier {
+        center: module.lit(Vec3::ZERO),
+        radius: module.lit(0.25),
+        dimension: ShapeDimension::Volume,
+    };
+
+    // Splinters fall down like everything else
+    let update_accel = AccelModifier::new(module.lit(Vec3::Y * -9.81));
+
+    // Keep the color of the prop and fade out at the end
+    let color = color.to_linear().to_vec4();
+    let mut gradient = Gradient::new();
+    gradient.add_key(0.0, color);
+    gradient.add_key(0.8, color);
+    gradient.add_key(1.0, color.with_w(0.0));
+    let color_over_lifetime = ColorOverLifetimeModifier {
+        gradient,
+        ..default()
+    };
+
+    let mut size_curve = Gradient::new();
+    size_curve.add_key(0.0, Vec3::splat(0.06));
+    size_curve.add_key(1.0, Vec3::splat(0.02));
+    let size_over_lifetime = SizeOverLifetimeModifier {
+        gradient: size_curve,
+        screen_space_size: false,
+    };
+
+    const PARTICLE_COUNT: f32 = 48.0;
+    let effect = EffectAsset::new(
+        PARTICLE_COUNT as u32,
+        SpawnerSettings::once(PARTICLE_COUNT.into()),
+        module,
+    )
+    .with_name("SplinterEffect")
+    .init(init_pos)
+    .init(velocity)
+    .init(lifetime)
+    .update(update_accel)
+    .render(orientation)
+    .render(color_over_lifetime)
+    .render(size_over_lifetime);
+
+    effects.add(effect)
+}
+
+#[cfg(feature = "native")]
+fn setup_fire_particles(effects: &mut Assets<EffectAsset>) -> Handle<EffectAsset> {
+    let writer = ExprWriter::new();
+
+    // Random upward velocity with some lateral randomness for flicker
+    let mean_velocity = writer.lit(Vec3::new(0.0, 1.5, 0.0));
+    let sd_velocity = writer.lit(Vec3::new(0.2, 0.5, 0.2));
+    let velocity = SetAttributeModifier::new(
+        Attribute::VELOCITY,
+        mean_velocity.normal(sd_velocity).expr(),
+    );
+
+    // Load the texture
+    let particle_texture_modifier = ParticleTextureModifier {
+        texture_slot: writer.lit(0u32).expr(),
+        sample_mapping: ImageSampleMapping::Modulate,
+    };
+
+    // Random rotation
+    let orientation = OrientModifier {
+        rotation: Some(writer.lit(0.0).uniform(writer.lit(TAU)).expr()),
+        mode: OrientMode::FaceCameraPosition,
+    };
+
+    let mut module = writer.finish();
+    module.add_texture_slot("shape");
+
+    // Spawn from small spherical area at the base
+    let init_pos = SetPositionSphereModifier {
+        center: module.lit(Vec3::Y * 0.2),
+        radius: module.lit(0.35),
+        dimension: ShapeDimension::Volume,
+    };
+
+    // Short lifetime for fire particles
+    let lifetime = SetAttributeModifier::new(Attribute::LIFETIME, module.lit(0.4));
+
+    // Constant upward acceleration (mimics heat rise)
+    let accel = module.lit(Vec3::Y * 0.4);
+    let update_accel = AccelModifier::new(accel);
+
+    // Additive blending to simulate light emission
+    let alpha_mode = bevy_hanabi::AlphaMode::Add;
+
+    // Color gradient for fire: transparent → bright yellow → orange → dark red → transparent
+    let mut gradient = Gradient::new();
+    gradient.add_key(0.0, Vec4::new(0.0, 0.0, 0.0, 0.0)); // transparent
+    gradient.add_key(0.1, Vec4::new(1.0, 0.8, 0.0, 1.0)); // bright yellow
+    gradient.add_key(0.3, Vec4::new(1.0, 0.4, 0.0, 1.0)); // orange
+    gradient.add_key(0.6, Vec4::new(0.6, 0.0, 0.0, 0.8)); // dark red
+    gradient.add_key(1.0, Vec4::new(0.0, 0.0, 0.0, 0.0)); // transparent
+    let color_over_lifetime = ColorOverLifetimeModifier {
+        gradient,
+        ..default()
+    };
+
+    // Size over lifetime modifier: small -> larger -> fade out
+    let mut size_curve = Gradient::new();
+    size_curve.add_key(0.0, Vec3::splat(0.2)); // start small
+    size_curve.add_key(0.3, Vec3::splat(0.5)); // grow
+    size_curve.add_key(1.0, Vec3::splat(0.0)); // shrink to nothing
+
+    let size_over_lifetime = SizeOverLifetimeModifier {
+        gradient: size_curve,
+        screen_space_size: false,
+    };
+
+    const MAX_PARTICLES: u32 = 32768;
+    let effect = EffectAsset::new(MAX_PARTICLES, SpawnerSettings::rate(150.0.into()), module)
+        .with_name("FireEffect")
+        .init(init_pos)
+        .init(velocity)
+        .init(lifetime)
+        .with_alpha_mode(alpha_mode)
+        .update(update_accel)
+        .render(orientation)
+        .render(color_over_lifetime)
+        .render(particle_texture_modifier)
+        .render(size_over_lifetime);
+
+    effects.add(effect)
+}
@@ -1,4 +1,3 @@
-use crate::third_party::bevy_trenchbroom::GetTrenchbroomModelPath as _;
 use crate::third_party::{avian3d::CollisionLayer, bevy_trenchbroom::fix_gltf_rotation};
 use avian3d::prelude::*;
 use bevy::{
@@ -6,93 +5,115 @@ use bevy::{
     prelude::*,
 };
 use bevy_tnua::TnuaNotPlatform;
-use bevy_trenchbroom::{class::QuakeClass, prelude::*};
+use bevy_trenchbroom::util::IsSceneWorld as _;
 
-pub(super) fn plugin(_app: &mut App) {}
-
-pub(crate) fn setup_dynamic_prop_with_convex_hull<T: QuakeClass>(
-    mut world: DeferredWorld,
-    entity: Entity,
-    _id: ComponentId,
-) {
-    if world.is_scene_world() {
-        return;
-    }
+#[cfg(feature = "native")]
+use super::effects::particle_bundle;
+use super::{
+    breakable::resolve_health,
+    definition::{PropBody, PropCollider, PropDefinitions},
+    effects::{Flicker, insert_not_shadow_caster, light_bundle, sound_bundle},
+};
 
-    let bundle = dynamic_bundle::<T>(&world, ColliderConstructor::ConvexHullFromMesh);
-    world
-        .commands()
-        .entity(entity)
-        .queue(fix_gltf_rotation)
-        .insert(bundle);
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<(Prop, DynamicProp)>();
 }
 
-pub(crate) fn setup_static_prop_with_convex_hull<T: QuakeClass>(
-    mut world: DeferredWorld,
-    entity: Entity,
-    _id: ComponentId,
-) {
-    if world.is_scene_world() {
-        return;
-    }
+/// A prop spawned from its definition in `assets/props.ron`.
+/// Holds the TrenchBroom classname of the prop.
+#[derive(Component, Debug, Clone, PartialEq, Eq, Reflect)]
+#[reflect(Component)]
+#[component(on_add = setup_prop)]
+pub(crate) struct Prop(pub(crate) String);
 
-    let bundle = static_bundle::<T>(&world, ColliderConstructor::ConvexHullFromMesh);
-    world
-        .commands()
-        .entity(entity)
-        .queue(fix_gltf_rotation)
-        .insert(bundle);
-}
+/// Marks props that can be pushed around and picked up.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+#[reflect(Component)]
+pub(crate) struct DynamicProp;
+
+/// About the density of oak wood (600-800 kg/m^3)
+const DEFAULT_DENSITY: f32 = 800.0;
 
-pub(crate) fn setup_static_prop_with_convex_decomposition<T: QuakeClass>(
-    mut world: DeferredWorld,
-    entity: Entity,
-    _id: ComponentId,
-) {
+fn setup_prop(mut world: DeferredWorld, entity: Entity, _id: ComponentId) {
     if world.is_scene_world() {
         return;
     }
+    let classname = &world.get::<Prop>(entity).unwrap().0;
+    let Some(definition) = world.resource::<PropDefinitions>().get(classname).cloned() else {
+        error!("Prop {classname:?} is not defined in props.ron");
+        return;
+    };
 
-    let bundle = static_bundle::<T>(&world, ColliderConstructor::ConvexDecompositionFromMesh);
-    world
-        .commands()
-        .entity(entity)
-        .queue(fix_gltf_rotation)
-        .insert(bundle);
-}
+    let model = world
+        .resource::<AssetServer>()
+        .load::<Scene>(definition.scene_path());
+    let sounds = definition
+        .sounds
+        .iter()
+        .map(|sound| sound_bundle(&world, sound))
+        .collect::<Vec<_>>();
+    #[cfg(feature = "native")]
+    let particles = definition
+        .particles
+        .iter()
+        .map(|particles| particle_bundle(&world, particles))
+        .collect::<Vec<_>>();
 
-pub(crate) fn dynamic_bundle<T: QuakeClass>(
-    world: &DeferredWorld,
-    constructor: ColliderConstructor,
-) -> impl Bundle {
-    let model = load_model::<T>(world);
-    (
-        TransformInterpolation,
-        ColliderConstructorHierarchy::new(constructor)
-            .with_default_layers(CollisionLayers::new(CollisionLayer::Prop, LayerMask::ALL))
-            // About the density of oak wood (600-800 kg/m^3)
-            .with_default_density(800.0),
-        RigidBody::Dynamic,
-        TnuaNotPlatform,
-        SceneRoot(model),
-    )
-}
+    let constructor = match definition.collider {
+        PropCollider::Hull => ColliderConstructor::ConvexHullFromMesh,
+        PropCollider::Decomposition => ColliderConstructor::ConvexDecompositionFromMesh,
+    };
+    let collider = ColliderConstructorHierarchy::new(constructor);
 
-pub(crate) fn static_bundle<T: QuakeClass>(
-    world: &DeferredWorld,
-    constructor: ColliderConstructor,
-) -> impl Bundle {
-    let model = load_model::<T>(world);
-    (
-        ColliderConstructorHierarchy::new(constructor).with_default_layers(CollisionLayers::new(
-            CollisionLayer::Default,
-            LayerMask::ALL,
-        )),
-        RigidBody::Static,
-        SceneRoot(model),
-    )
-}
+    let mut commands = world.commands();
+    let mut entity_commands = commands.entity(entity);
+    entity_commands
+        .queue(fix_gltf_rotation)
+        .queue(resolve_health(definition.breakable.clone()))
+        .insert((SceneRoot(model), definition.material));
+    match definition.body {
+        PropBody::Static => {
+            let collider = collider.with_default_layers(CollisionLayers::new(
+                CollisionLayer::Default,
+                LayerMask::ALL,
+            ));
+            let collider = match definition.density {
+                Some(density) => collider.with_default_density(density),
+                None => collider,
+            };
+            entity_commands.insert((collider, RigidBody::Static));
+        }
+        PropBody::Dynamic => {
+            entity_commands.insert((
+                TransformInterpolation,
+                collider
+                    .with_default_layers(CollisionLayers::new(CollisionLayer::Prop, LayerMask::ALL))
+                    .with_default_density(definition.density.unwrap_or(DEFAULT_DENSITY)),
+                RigidBody::Dynamic,
+                TnuaNotPlatform,
+                DynamicProp,
+            ));
+        }
+    }
+    if !definition.shadows {
+        entity_commands.observe(insert_not_shadow_caster);
+    }
 
-fn load_model<T: QuakeClass>(world: &DeferredWorld) -> Handle<Scene> {
-    world.resource::<AssetServer>().load(T::scene_path())
+    entity_commands.with_children(|parent| {
+        for light in &definition.lights {
+            let mut light_entity = parent.spawn(light_bundle(light));
+            if light.flicker {
+                light_entity.insert(Flicker {
+                    base_intensity: light.intensity,
+                });
+            }
+        }
+        for sound in sounds {
+            parent.spawn(sound);
+        }
+        #[cfg(feature = "native")]
+        for particles in particles {
+            parent.spawn(particles);
+        }
+    });
 }
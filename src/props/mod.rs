@@ -1,103 +1,97 @@
-use bevy::prelude::*;
-use bevy_trenchbroom::config::TrenchBroomConfig;
-use generic::*;
-use specific::*;
+//! Props are the models placed in levels, like furniture and crates.
+//! They are defined in `assets/props.ron`, from which we generate a TrenchBroom class for each of them.
 
+use bevy::prelude::*;
+use bevy_trenchbroom::{
+    class::{ErasedQuakeClass, QuakeClass, QuakeClassInfo, QuakeClassSpawnView},
+    config::TrenchBroomConfig,
+    prelude::*,
+    qmap::QuakeMapEntity,
+};
+
+use crate::gameplay::save::SaveKey;
+
+use breakable::Breakable;
+pub(crate) use breakable::PropBroken;
+use definition::PropDefinition;
+pub(crate) use definition::PropDefinitions;
+pub(crate) use generic::{DynamicProp, Prop};
+
+mod breakable;
+mod definition;
 mod effects;
 mod generic;
-mod specific;
 
 pub(super) fn plugin(app: &mut App) {
-    app.add_plugins((generic::plugin, specific::plugin, effects::plugin));
+    app.register_type::<(PropTemplate, BreakablePropTemplate)>();
+    app.add_plugins((
+        definition::plugin,
+        generic::plugin,
+        effects::plugin,
+        breakable::plugin,
+    ));
 }
 
-// We can define a new prop here to make it show up in TrenchBroom.
-// Afterwards, we still need to add it to the `LevelAssets` struct to preload it for a given level.
-
 pub(crate) trait RegisterProps {
-    fn register_props(self) -> TrenchBroomConfig;
+    fn register_props(self, definitions: &PropDefinitions) -> TrenchBroomConfig;
 }
 
 impl RegisterProps for TrenchBroomConfig {
-    fn register_props(self) -> TrenchBroomConfig {
-        self.register_class::<BurningLogs>()
-            .register_class::<Grate>()
-            .register_class::<Table>()
-            .register_class::<Chair>()
-            .register_class::<Bookshelf>()
-            .register_class::<LampSitting>()
-            .register_class::<Crate>()
+    fn register_props(mut self, definitions: &PropDefinitions) -> TrenchBroomConfig {
+        for (classname, definition) in definitions.iter() {
+            let class = if definition.breakable.is_some() {
+                prop_class::<BreakablePropTemplate>(classname, definition)
+            } else {
+                prop_class::<PropTemplate>(classname, definition)
+            };
+            self.class_map.insert(class.info.name, class);
+        }
+        self
     }
 }
 
-// generic dynamic props
-
-create_prop!(
-    Crate,
-    "models/darkmod/containers/crate01.gltf",
-    on_add = setup_dynamic_prop_with_convex_hull::<Crate>
-);
-
-// generic static props
-
-create_prop!(
-    Grate,
-    "models/darkmod/fireplace/grate.gltf",
-    on_add = setup_static_prop_with_convex_hull::<Grate>
-);
-
-create_prop!(
-    Table,
-    "models/darkmod/furniture/tables/rtable1.gltf",
-    on_add = setup_static_prop_with_convex_decomposition::<Table>
-);
-
-create_prop!(
-    Bookshelf,
-    "models/darkmod/furniture/shelves/bookshelf02.gltf",
-    on_add = setup_static_prop_with_convex_hull::<Bookshelf>
-);
-
-// props with a specific setup function
-
-create_prop!(
-    LampSitting,
-    "models/darkmod/lights/non-extinguishable/round_lantern_sitting.gltf",
-    on_add = setup_lamp_sitting
-);
-
-create_prop!(
-    Chair,
-    "models/darkmod/furniture/seating/wchair1.gltf",
-    on_add = specific::setup_chair
-);
-
-create_prop!(
-    BurningLogs,
-    "models/darkmod/fireplace/burntwood.gltf",
-    on_add = specific::setup_burning_logs
-);
+/// Generates the TrenchBroom class of a prop from one of the templates below.
+fn prop_class<T: QuakeClass>(
+    classname: &str,
+    definition: &PropDefinition,
+) -> &'static ErasedQuakeClass {
+    // Classes are usually known at compile time, so bevy_trenchbroom expects them to live forever.
+    // We only register them once at startup, so leaking them is fine.
+    Box::leak(Box::new(ErasedQuakeClass {
+        info: QuakeClassInfo {
+            name: classname.to_string().leak(),
+            model: Some(format!("{:?}", definition.model).leak()),
+            ..T::CLASS_INFO
+        },
+        spawn_fn: spawn_prop::<T>,
+        ..ErasedQuakeClass::of::<T>()
+    }))
+}
 
-// This macro does nothing fancy, it's just here to save us some boilerplate when defining new prop classes :)
-macro_rules! create_prop {
-    ($name:ident, $model:expr, on_add = $on_add:ty) => {
-        #[derive(
-            bevy_trenchbroom::prelude::PointClass,
-            Component,
-            Debug,
-            Clone,
-            Copy,
-            PartialEq,
-            Eq,
-            Default,
-            Reflect,
-        )]
-        #[reflect(Component)]
-        #[base(Transform, Visibility)]
-        #[model($model)]
-        #[component(on_add = $on_add)]
-        pub(crate) struct $name;
-    };
+/// The TrenchBroom class that props are generated from.
+/// It is never registered itself, so it does not show up in TrenchBroom.
+#[derive(PointClass, Component, Debug, Clone, Copy, Default, Reflect)]
+#[reflect(Component)]
+#[base(Transform, Visibility)]
+struct PropTemplate;
+
+/// Like [`PropTemplate`], but for props that are `breakable` in `props.ron`,
+/// so that only they get a `health` property in TrenchBroom.
+#[derive(PointClass, Component, Debug, Clone, Copy, Default, Reflect)]
+#[reflect(Component)]
+#[base(Transform, Visibility, Breakable)]
+struct BreakablePropTemplate;
+
+/// Spawns a prop like its template, and then remembers which prop it actually is.
+fn spawn_prop<T: QuakeClass>(
+    config: &TrenchBroomConfig,
+    src_entity: &QuakeMapEntity,
+    view: &mut QuakeClassSpawnView,
+) -> anyhow::Result<()> {
+    (ErasedQuakeClass::of::<T>().spawn_fn)(config, src_entity, view)?;
+    let classname = src_entity.classname()?.to_string();
+    view.world
+        .entity_mut(view.entity)
+        .insert((Prop(classname), SaveKey::from_map_entity(src_entity)));
+    Ok(())
 }
-// This `use` allows us to use the macro before its definition.
-use create_prop;
@@ -3,6 +3,7 @@
 use bevy::prelude::*;
 
 use crate::{
+    AppSet,
     asset_tracking::LoadResource,
     audio::Music,
     screens::Screen,
@@ -13,8 +14,14 @@ pub(super) fn plugin(app: &mut App) {
     app.add_systems(OnEnter(Screen::Credits), spawn_credits_screen);
 
     app.register_type::<CreditsMusic>();
-    app.load_resource::<CreditsMusic>();
-    app.add_systems(OnEnter(Screen::Credits), start_credits_music);
+    // The credits are rarely visited, so don't make the loading screen wait for their music.
+    app.load_resource_in_background::<CreditsMusic>();
+    app.add_systems(
+        Update,
+        start_credits_music
+            .run_if(in_state(Screen::Credits).and(resource_exists::<CreditsMusic>))
+            .in_set(AppSet::Update),
+    );
     app.add_systems(OnExit(Screen::Credits), stop_credits_music);
 }
 
@@ -60,6 +67,9 @@ impl FromWorld for CreditsMusic {
 }
 
 fn start_credits_music(mut commands: Commands, mut music: ResMut<CreditsMusic>) {
+    if music.entity.is_some() {
+        return;
+    }
     music.entity = Some(
         commands
             .spawn((
@@ -71,8 +81,8 @@ fn start_credits_music(mut commands: Commands, mut music: ResMut<CreditsMusic>)
     );
 }
 
-fn stop_credits_music(mut commands: Commands, mut music: ResMut<CreditsMusic>) {
-    if let Some(entity) = music.entity.take() {
+fn stop_credits_music(mut commands: Commands, music: Option<ResMut<CreditsMusic>>) {
+    if let Some(entity) = music.and_then(|mut music| music.entity.take()) {
         commands.entity(entity).despawn();
     }
 }
@@ -1,21 +1,20 @@
 //! The screen state for the main gameplay.
 
-use bevy::{audio::Volume, input::common_conditions::input_just_pressed, prelude::*};
+use bevy::{audio::Volume, prelude::*};
 
 use crate::{AppSet, asset_tracking::LoadResource, audio::Music, screens::Screen};
 
 pub(super) fn plugin(app: &mut App) {
     app.register_type::<GameplayMusic>();
-    app.load_resource::<GameplayMusic>();
-    app.add_systems(OnEnter(Screen::SpawnLevel), start_gameplay_music);
-    app.add_systems(OnExit(Screen::Gameplay), stop_gameplay_music);
-
+    // The game is playable without music, so don't make the loading screen wait for it.
+    app.load_resource_in_background::<GameplayMusic>();
     app.add_systems(
         Update,
-        return_to_title_screen
-            .run_if(in_state(Screen::Gameplay).and(input_just_pressed(KeyCode::Escape)))
+        start_gameplay_music
+            .run_if(in_state(Screen::Gameplay).and(resource_exists::<GameplayMusic>))
             .in_set(AppSet::Update),
     );
+    app.add_systems(OnExit(Screen::Gameplay), stop_gameplay_music);
 }
 
 #[derive(Resource, Asset, Clone, Reflect)]
@@ -37,6 +36,9 @@ impl FromWorld for GameplayMusic {
 }
 
 fn start_gameplay_music(mut commands: Commands, mut music: ResMut<GameplayMusic>) {
+    if music.entity.is_some() {
+        return;
+    }
     music.entity = Some(
         commands
             .spawn((
@@ -48,12 +50,8 @@ fn start_gameplay_music(mut commands: Commands, mut music: ResMut<GameplayMusic>
     );
 }
 
-fn stop_gameplay_music(mut commands: Commands, mut music: ResMut<GameplayMusic>) {
-    if let Some(entity) = music.entity.take() {
+fn stop_gameplay_music(mut commands: Commands, music: Option<ResMut<GameplayMusic>>) {
+    if let Some(entity) = music.and_then(|mut music| music.entity.take()) {
         commands.entity(entity).despawn();
     }
 }
-
-fn return_to_title_screen(mut next_screen: ResMut<NextState<Screen>>) {
-    next_screen.set(Screen::Title);
-}
@@ -1,39 +1,151 @@
 //! A loading screen during which game assets are loaded.
 //! This reduces stuttering, especially for audio on WASM.
+//!
+//! At startup, this loads the assets shared by all levels and continues to the title screen.
+//! When a [`CurrentLevel`] has been chosen, it also loads that level and continues to spawn it.
 
-use bevy::prelude::*;
+use bevy::{prelude::*, ui::Val::*};
 use bevy_yarnspinner::prelude::YarnProject;
 
 use crate::{
-    AppSet, asset_tracking::ResourceHandles, gameplay::player::camera::PlayerCameraParent,
-    screens::Screen, theme::prelude::*,
+    AppSet,
+    asset_tracking::{LoadingProgress, ResourceHandles, any_resource_failed, loading_timed_out},
+    gameplay::level::{CurrentLevel, LevelRegistry},
+    screens::Screen,
+    theme::{palette::*, prelude::*},
 };
 
 pub(super) fn plugin(app: &mut App) {
     app.add_systems(OnEnter(Screen::Loading), spawn_loading_screen);
+    app.add_systems(
+        Update,
+        update_loading_screen
+            .run_if(in_state(Screen::Loading))
+            .in_set(AppSet::ChangeUi),
+    );
 
     app.add_systems(
         Update,
-        continue_to_title_screen
-            .run_if(in_state(Screen::Loading).and(all_assets_loaded))
+        (
+            continue_to_next_screen.run_if(all_assets_loaded),
+            show_loading_failed_screen.run_if(any_resource_failed.or(loading_timed_out)),
+        )
+            .run_if(in_state(Screen::Loading))
             .in_set(AppSet::Update),
     );
 }
 
-fn spawn_loading_screen(mut commands: Commands) {
+fn spawn_loading_screen(
+    mut commands: Commands,
+    current_level: Option<Res<CurrentLevel>>,
+    registry: Res<LevelRegistry>,
+) {
+    let text = match current_level
+        .as_ref()
+        .and_then(|current_level| registry.get(&current_level.0))
+    {
+        Some(level) => format!("Loading {}...", level.display_name),
+        None => "Loading...".to_string(),
+    };
     commands
         .ui_root()
         .insert(StateScoped(Screen::Loading))
         .with_children(|parent| {
-            parent.label("Loading...").insert(Node {
+            parent.label(text).insert(Node {
                 justify_content: JustifyContent::Center,
                 ..default()
             });
+            parent
+                .spawn((
+                    Name::new("Progress Bar"),
+                    Node {
+                        width: Px(500.0),
+                        height: Px(20.0),
+                        ..default()
+                    },
+                    BackgroundColor(NODE_BACKGROUND),
+                ))
+                .with_children(|parent| {
+                    parent.spawn((
+                        Name::new("Progress Bar Fill"),
+                        Node {
+                            width: Percent(0.0),
+                            height: Percent(100.0),
+                            ..default()
+                        },
+                        BackgroundColor(SLIDER_FILL),
+                        ProgressBarFill,
+                    ));
+                });
+            parent.label("").insert(LoadingStatus);
+            parent
+                .label("")
+                .insert((TextFont::from_font_size(16.0), PendingList));
         });
 }
 
-fn continue_to_title_screen(mut next_screen: ResMut<NextState<Screen>>) {
-    next_screen.set(Screen::Title);
+#[derive(Component)]
+struct ProgressBarFill;
+
+#[derive(Component)]
+struct LoadingStatus;
+
+/// Lists the resources that are still loading.
+#[derive(Component)]
+struct PendingList;
+
+fn update_loading_screen(
+    resource_handles: Res<ResourceHandles>,
+    mut fill: Single<&mut Node, With<ProgressBarFill>>,
+    mut status: Single<&mut Text, (With<LoadingStatus>, Without<PendingList>)>,
+    mut pending_list: Single<&mut Text, (With<PendingList>, Without<LoadingStatus>)>,
+) {
+    let progress = resource_handles.progress();
+    fill.width = Percent(progress.fraction() * 100.0);
+    status.0 = status_text(progress);
+    pending_list.0 = pending_text(progress);
+}
+
+fn status_text(progress: &LoadingProgress) -> String {
+    let megabytes = progress.loaded_bytes as f32 / 1_000_000.0;
+    let mut text = format!(
+        "{finished} / {total} resources ({megabytes:.1} MB audio)",
+        finished = progress.finished,
+        total = progress.total,
+    );
+    if let Some(current) = progress.current {
+        text.push_str(&format!("\nLoading {current}"));
+    }
+    text
+}
+
+fn pending_text(progress: &LoadingProgress) -> String {
+    progress
+        .pending
+        .iter()
+        .map(|pending| {
+            format!(
+                "{}: {} / {}",
+                pending.type_name, pending.loaded_dependencies, pending.total_dependencies
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn continue_to_next_screen(
+    current_level: Option<Res<CurrentLevel>>,
+    mut next_screen: ResMut<NextState<Screen>>,
+) {
+    if current_level.is_some() {
+        next_screen.set(Screen::SpawnLevel);
+    } else {
+        next_screen.set(Screen::Title);
+    }
+}
+
+fn show_loading_failed_screen(mut next_screen: ResMut<NextState<Screen>>) {
+    next_screen.set(Screen::LoadingFailed);
 }
 
 fn all_assets_loaded(
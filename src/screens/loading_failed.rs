@@ -0,0 +1,100 @@
+//! An error screen that lists the assets that failed to load
+//! and lets the player continue with placeholders instead.
+//! Also shown when loading takes too long, in which case the player can keep waiting.
+
+use bevy::prelude::*;
+
+use crate::{
+    asset_tracking::{
+        FailedResource, LoadingProgress, ResourceHandles, insert_failed_resources_with_placeholders,
+    },
+    screens::Screen,
+    theme::{interaction::OnPress, prelude::*},
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(OnEnter(Screen::LoadingFailed), spawn_loading_failed_screen);
+}
+
+fn spawn_loading_failed_screen(mut commands: Commands, resource_handles: Res<ResourceHandles>) {
+    let (header, details) = if resource_handles.failed().is_empty() {
+        (
+            "Loading timed out",
+            timeout_text(resource_handles.progress()),
+        )
+    } else {
+        (
+            "Failed to load assets",
+            failure_text(resource_handles.failed()),
+        )
+    };
+    commands
+        .ui_root()
+        .insert(StateScoped(Screen::LoadingFailed))
+        .with_children(|parent| {
+            parent.header(header);
+            parent.label(details);
+            parent
+                .button("Continue")
+                .observe(continue_with_placeholders);
+
+            #[cfg(not(target_family = "wasm"))]
+            parent.button("Exit").observe(exit_app);
+        });
+}
+
+fn failure_text(failed: &[FailedResource]) -> String {
+    failed
+        .iter()
+        .flat_map(|failed| {
+            failed
+                .failed_paths
+                .iter()
+                .map(move |path| format!("{path} (needed by {})", failed.type_name()))
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Lists the assets that failed to load if we know about any yet,
+/// otherwise the resources that are still loading.
+fn timeout_text(progress: &LoadingProgress) -> String {
+    let failed: Vec<_> = progress
+        .pending
+        .iter()
+        .flat_map(|pending| {
+            pending
+                .failed
+                .iter()
+                .map(move |path| format!("{path} (needed by {})", pending.type_name))
+        })
+        .collect();
+    if !failed.is_empty() {
+        return format!("Failed to load:\n{}", failed.join("\n"));
+    }
+    let pending: Vec<_> = progress
+        .pending
+        .iter()
+        .map(|pending| pending.type_name)
+        .collect();
+    format!(
+        "Still waiting for:\n{}\nContinue to keep waiting.",
+        pending.join("\n")
+    )
+}
+
+fn continue_with_placeholders(
+    _trigger: Trigger<OnPress>,
+    mut commands: Commands,
+    mut resource_handles: ResMut<ResourceHandles>,
+    mut next_screen: ResMut<NextState<Screen>>,
+) {
+    resource_handles.restart_timeout();
+    commands.queue(insert_failed_resources_with_placeholders);
+    next_screen.set(Screen::Loading);
+}
+
+#[cfg(not(target_family = "wasm"))]
+fn exit_app(_trigger: Trigger<OnPress>, mut app_exit: EventWriter<AppExit>) {
+    app_exit.send(AppExit::Success);
+}
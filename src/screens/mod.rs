@@ -3,6 +3,8 @@
 mod credits;
 mod gameplay;
 mod loading;
+mod loading_failed;
+pub(crate) mod settings;
 mod spawn_level;
 mod splash;
 mod title;
@@ -18,6 +20,8 @@ pub(super) fn plugin(app: &mut App) {
         gameplay::plugin,
         spawn_level::plugin,
         loading::plugin,
+        loading_failed::plugin,
+        settings::plugin,
         splash::plugin,
         title::plugin,
     ));
@@ -29,8 +33,10 @@ pub(crate) enum Screen {
     #[default]
     Splash,
     Loading,
+    LoadingFailed,
     Title,
     Credits,
+    Settings,
     SpawnLevel,
     Gameplay,
 }
@@ -0,0 +1,92 @@
+//! A settings screen that can be accessed from the title screen.
+//! The same settings are also available from the pause menu.
+
+use bevy::prelude::*;
+
+use crate::{
+    screens::Screen,
+    settings::{Settings, WindowModeSetting},
+    theme::{interaction::OnPress, prelude::*},
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(OnEnter(Screen::Settings), spawn_settings_screen);
+}
+
+fn spawn_settings_screen(mut commands: Commands, settings: Res<Settings>) {
+    commands
+        .ui_root()
+        .insert(StateScoped(Screen::Settings))
+        .with_children(|parent| {
+            spawn_settings_widgets(parent, &settings);
+            parent.button("Back").observe(enter_title_screen);
+        });
+}
+
+/// Spawns a header and the widgets for all user-facing settings.
+pub(crate) fn spawn_settings_widgets(parent: &mut ChildBuilder, settings: &Settings) {
+    parent.header("Settings");
+
+    parent
+        .slider("Master Volume", settings.master_volume, 0.0..=1.0, 0.05)
+        .observe(set_master_volume);
+    parent
+        .slider("Music Volume", settings.music_volume, 0.0..=1.0, 0.05)
+        .observe(set_music_volume);
+    parent
+        .slider("SFX Volume", settings.sound_effect_volume, 0.0..=1.0, 0.05)
+        .observe(set_sound_effect_volume);
+    parent
+        .slider("Sensitivity", settings.mouse_sensitivity, 0.1..=3.0, 0.1)
+        .observe(set_mouse_sensitivity);
+    parent
+        .slider("Field of View", settings.fov, 60.0..=120.0, 1.0)
+        .observe(set_fov);
+
+    let window_modes = WindowModeSetting::ALL
+        .iter()
+        .map(|mode| mode.label().to_string())
+        .collect();
+    let selected_window_mode = WindowModeSetting::ALL
+        .iter()
+        .position(|mode| *mode == settings.window_mode)
+        .unwrap_or_default();
+    parent
+        .dropdown("Window Mode", window_modes, selected_window_mode)
+        .observe(set_window_mode);
+    parent.checkbox("VSync", settings.vsync).observe(set_vsync);
+}
+
+fn set_master_volume(trigger: Trigger<OnSliderChange>, mut settings: ResMut<Settings>) {
+    settings.master_volume = trigger.0;
+}
+
+fn set_music_volume(trigger: Trigger<OnSliderChange>, mut settings: ResMut<Settings>) {
+    settings.music_volume = trigger.0;
+}
+
+fn set_sound_effect_volume(trigger: Trigger<OnSliderChange>, mut settings: ResMut<Settings>) {
+    settings.sound_effect_volume = trigger.0;
+}
+
+fn set_mouse_sensitivity(trigger: Trigger<OnSliderChange>, mut settings: ResMut<Settings>) {
+    settings.mouse_sensitivity = trigger.0;
+}
+
+fn set_fov(trigger: Trigger<OnSliderChange>, mut settings: ResMut<Settings>) {
+    settings.fov = trigger.0;
+}
+
+fn set_window_mode(trigger: Trigger<OnSelect>, mut settings: ResMut<Settings>) {
+    if let Some(mode) = WindowModeSetting::ALL.get(trigger.0) {
+        settings.window_mode = *mode;
+    }
+}
+
+fn set_vsync(trigger: Trigger<OnToggle>, mut settings: ResMut<Settings>) {
+    settings.vsync = trigger.0;
+}
+
+fn enter_title_screen(_trigger: Trigger<OnPress>, mut next_screen: ResMut<NextState<Screen>>) {
+    next_screen.set(Screen::Title);
+}
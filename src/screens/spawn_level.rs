@@ -1,9 +1,12 @@
-//! The title screen that appears when the game starts.
+//! The screen that is shown while the [`CurrentLevel`] is being spawned.
 
 use bevy::prelude::*;
 
 use crate::{
-    gameplay::{level::spawn_level as spawn_level_command, player::camera::PlayerCameraParent},
+    gameplay::{
+        level::{CurrentLevel, SpawnLevel},
+        player::camera::PlayerCameraParent,
+    },
     screens::Screen,
     theme::prelude::*,
 };
@@ -13,7 +16,6 @@ pub(super) fn plugin(app: &mut App) {
         OnEnter(Screen::SpawnLevel),
         (spawn_level, spawn_spawn_level_screen),
     );
-    app.add_systems(OnEnter(Screen::SpawnLevel), spawn_spawn_level_screen);
     app.add_systems(
         Update,
         advance_to_gameplay_screen.run_if(in_state(Screen::SpawnLevel)),
@@ -29,8 +31,8 @@ fn spawn_spawn_level_screen(mut commands: Commands) {
         });
 }
 
-fn spawn_level(mut commands: Commands) {
-    commands.queue(spawn_level_command);
+fn spawn_level(mut commands: Commands, current_level: Res<CurrentLevel>) {
+    commands.queue(SpawnLevel(current_level.0.clone()));
 }
 
 fn advance_to_gameplay_screen(
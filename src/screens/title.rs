@@ -3,6 +3,10 @@
 use bevy::prelude::*;
 
 use crate::{
+    gameplay::{
+        level::{CurrentLevel, LevelRegistry},
+        save::{PendingSave, SaveGame},
+    },
     screens::Screen,
     theme::{interaction::OnPress, prelude::*},
 };
@@ -11,12 +15,16 @@ pub(super) fn plugin(app: &mut App) {
     app.add_systems(OnEnter(Screen::Title), spawn_title_screen);
 }
 
-fn spawn_title_screen(mut commands: Commands) {
+fn spawn_title_screen(mut commands: Commands, registry: Res<LevelRegistry>) {
     commands
         .ui_root()
         .insert(StateScoped(Screen::Title))
         .with_children(|children| {
-            children.button("Play").observe(enter_spawn_level_screen);
+            if SaveGame::load(&registry).is_some() {
+                children.button("Continue").observe(continue_saved_game);
+            }
+            children.button("New Game").observe(start_new_game);
+            children.button("Settings").observe(enter_settings_screen);
             children.button("Credits").observe(enter_credits_screen);
 
             #[cfg(not(target_family = "wasm"))]
@@ -24,11 +32,32 @@ fn spawn_title_screen(mut commands: Commands) {
         });
 }
 
-fn enter_spawn_level_screen(
+fn continue_saved_game(
     _trigger: Trigger<OnPress>,
+    mut commands: Commands,
     mut next_screen: ResMut<NextState<Screen>>,
+    registry: Res<LevelRegistry>,
 ) {
-    next_screen.set(Screen::SpawnLevel);
+    let Some(save) = SaveGame::load(&registry) else {
+        return;
+    };
+    commands.insert_resource(CurrentLevel(save.level.clone()));
+    commands.insert_resource(PendingSave(save));
+    next_screen.set(Screen::Loading);
+}
+
+fn start_new_game(
+    _trigger: Trigger<OnPress>,
+    mut commands: Commands,
+    mut next_screen: ResMut<NextState<Screen>>,
+) {
+    commands.remove_resource::<PendingSave>();
+    commands.insert_resource(CurrentLevel::default());
+    next_screen.set(Screen::Loading);
+}
+
+fn enter_settings_screen(_trigger: Trigger<OnPress>, mut next_screen: ResMut<NextState<Screen>>) {
+    next_screen.set(Screen::Settings);
 }
 
 fn enter_credits_screen(_trigger: Trigger<OnPress>, mut next_screen: ResMut<NextState<Screen>>) {
@@ -0,0 +1,204 @@
+//! User settings that are persisted between sessions through [`storage`].
+//!
+//! Systems that depend on a setting should read the [`Settings`] resource and
+//! run again whenever it changes, so that changes are applied live.
+
+use bevy::{
+    prelude::*,
+    window::{PresentMode, WindowMode},
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{menus::Menu, screens::Screen, storage};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<Settings>();
+    app.insert_resource(Settings::load());
+    app.add_systems(
+        Update,
+        apply_window_settings.run_if(resource_changed::<Settings>),
+    );
+    // Sliders change the settings every frame while dragged,
+    // so we only write them to storage once the player is done with them.
+    app.add_systems(OnExit(Screen::Settings), save_settings);
+    app.add_systems(OnExit(Menu::Settings), save_settings);
+}
+
+#[derive(Resource, Reflect, Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[reflect(Resource)]
+// Fall back to the default for each missing field, so that old settings files stay valid
+// when new settings are added.
+#[serde(default)]
+pub(crate) struct Settings {
+    /// Volume multiplier for all audio.
+    pub(crate) master_volume: f32,
+    /// Volume multiplier for audio tagged with [`Music`](crate::audio::Music).
+    pub(crate) music_volume: f32,
+    /// Volume multiplier for audio tagged with [`SoundEffect`](crate::audio::SoundEffect).
+    pub(crate) sound_effect_volume: f32,
+    /// Multiplier for the default mouse and gamepad camera sensitivity.
+    pub(crate) mouse_sensitivity: f32,
+    /// Vertical field of view of the world model camera in degrees.
+    pub(crate) fov: f32,
+    pub(crate) window_mode: WindowModeSetting,
+    pub(crate) vsync: bool,
+    pub(crate) keybinds: Keybinds,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            master_volume: 0.3,
+            music_volume: 1.0,
+            sound_effect_volume: 1.0,
+            mouse_sensitivity: 1.0,
+            fov: 90.0,
+            window_mode: default(),
+            vsync: true,
+            keybinds: default(),
+        }
+    }
+}
+
+/// How the game window is shown.
+#[derive(Reflect, Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub(crate) enum WindowModeSetting {
+    #[default]
+    Windowed,
+    /// A window without decorations that covers the whole monitor.
+    Borderless,
+    /// Takes exclusive control of the monitor.
+    Fullscreen,
+}
+
+impl WindowModeSetting {
+    /// All modes, in the order they are listed in the settings.
+    pub(crate) const ALL: [Self; 3] = [Self::Windowed, Self::Borderless, Self::Fullscreen];
+
+    pub(crate) fn label(self) -> &'static str {
+        match self {
+            Self::Windowed => "Windowed",
+            Self::Borderless => "Borderless",
+            Self::Fullscreen => "Fullscreen",
+        }
+    }
+
+    fn window_mode(self) -> WindowMode {
+        match self {
+            Self::Windowed => WindowMode::Windowed,
+            Self::Borderless => WindowMode::BorderlessFullscreen(MonitorSelection::Current),
+            Self::Fullscreen => WindowMode::Fullscreen(MonitorSelection::Current),
+        }
+    }
+}
+
+/// Keyboard bindings for the player's actions.
+/// Movement is always bound to WASD and gamepad bindings are not configurable.
+/// All other keys used by gameplay actions belong here, so that dialogue and menus can block them.
+#[derive(Reflect, Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub(crate) struct Keybinds {
+    pub(crate) jump: KeyCode,
+    pub(crate) sprint: KeyCode,
+    pub(crate) crouch: KeyCode,
+    pub(crate) interact: KeyCode,
+}
+
+impl Keybinds {
+    pub(crate) fn keys(&self) -> [KeyCode; 4] {
+        [self.jump, self.sprint, self.crouch, self.interact]
+    }
+}
+
+impl Default for Keybinds {
+    fn default() -> Self {
+        Self {
+            jump: KeyCode::Space,
+            sprint: KeyCode::ShiftLeft,
+            crouch: KeyCode::ControlLeft,
+            interact: KeyCode::KeyE,
+        }
+    }
+}
+
+const STORAGE_NAME: &str = "settings";
+
+impl Settings {
+    /// Loads the settings from storage, falling back to the defaults if there are none yet
+    /// or if they could not be read.
+    fn load() -> Self {
+        let Some(serialized) = storage::read(STORAGE_NAME) else {
+            return Self::default();
+        };
+        match ron::from_str(&serialized) {
+            Ok(settings) => settings,
+            Err(err) => {
+                warn!("Failed to parse settings, using defaults instead: {err}");
+                Self::default()
+            }
+        }
+    }
+
+    fn save(&self) {
+        let serialized = match ron::ser::to_string_pretty(self, default()) {
+            Ok(serialized) => serialized,
+            Err(err) => {
+                error!("Failed to serialize settings: {err}");
+                return;
+            }
+        };
+        storage::write(STORAGE_NAME, &serialized);
+    }
+}
+
+fn save_settings(settings: Res<Settings>) {
+    settings.save();
+}
+
+fn apply_window_settings(settings: Res<Settings>, mut window: Single<&mut Window>) {
+    let mode = settings.window_mode.window_mode();
+    if window.mode != mode {
+        window.mode = mode;
+    }
+    let present_mode = if settings.vsync {
+        PresentMode::AutoVsync
+    } else {
+        PresentMode::AutoNoVsync
+    };
+    if window.present_mode != present_mode {
+        window.present_mode = present_mode;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn missing_settings_use_defaults() {
+        let settings: Settings = ron::from_str("(fov: 70.0, keybinds: (jump: KeyE))").unwrap();
+        assert_eq!(
+            settings,
+            Settings {
+                fov: 70.0,
+                keybinds: Keybinds {
+                    jump: KeyCode::KeyE,
+                    ..default()
+                },
+                ..default()
+            }
+        );
+    }
+
+    #[test]
+    fn settings_survive_ron_round_trip() {
+        let settings = Settings {
+            master_volume: 0.8,
+            window_mode: WindowModeSetting::Borderless,
+            vsync: false,
+            ..default()
+        };
+        let serialized = ron::ser::to_string_pretty(&settings, default()).unwrap();
+        assert_eq!(ron::from_str::<Settings>(&serialized).unwrap(), settings);
+    }
+}
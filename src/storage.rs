@@ -0,0 +1,84 @@
+//! Persistent key-value storage for serialized data such as settings and save games.
+//! Natively, each entry is stored as a RON file in the platform's config directory.
+//! On the web, entries are stored in the browser's local storage.
+
+#[cfg(not(target_family = "wasm"))]
+mod native {
+    use std::{fs, path::PathBuf};
+
+    use bevy::prelude::*;
+
+    fn path(name: &str) -> Option<PathBuf> {
+        Some(
+            dirs::config_dir()?
+                .join("foxtrot")
+                .join(format!("{name}.ron")),
+        )
+    }
+
+    pub(crate) fn read(name: &str) -> Option<String> {
+        let path = path(name)?;
+        fs::read_to_string(&path)
+            .inspect_err(|err| {
+                if err.kind() != std::io::ErrorKind::NotFound {
+                    warn!("Failed to read {}: {err}", path.display());
+                }
+            })
+            .ok()
+    }
+
+    pub(crate) fn write(name: &str, serialized: &str) {
+        let Some(path) = path(name) else {
+            warn!("Could not determine the config directory, {name} will not be saved.");
+            return;
+        };
+        if let Err(err) = path.parent().map_or(Ok(()), fs::create_dir_all) {
+            error!("Failed to create the config directory: {err}");
+            return;
+        }
+        if let Err(err) = fs::write(&path, serialized) {
+            error!("Failed to write {}: {err}", path.display());
+        }
+    }
+
+    pub(crate) fn exists(name: &str) -> bool {
+        path(name).is_some_and(|path| path.is_file())
+    }
+}
+
+#[cfg(not(target_family = "wasm"))]
+pub(crate) use native::*;
+
+#[cfg(target_family = "wasm")]
+mod web {
+    use bevy::prelude::*;
+
+    fn key(name: &str) -> String {
+        format!("foxtrot_{name}")
+    }
+
+    fn local_storage() -> Option<web_sys::Storage> {
+        web_sys::window()?.local_storage().ok().flatten()
+    }
+
+    pub(crate) fn read(name: &str) -> Option<String> {
+        local_storage()?.get_item(&key(name)).ok().flatten()
+    }
+
+    pub(crate) fn write(name: &str, serialized: &str) {
+        let Some(storage) = local_storage() else {
+            warn!("Local storage is not available, {name} will not be saved.");
+            return;
+        };
+        if let Err(err) = storage.set_item(&key(name), serialized) {
+            error!("Failed to write {name} to local storage: {err:?}");
+        }
+    }
+
+    pub(crate) fn exists(name: &str) -> bool {
+        read(name).is_some()
+    }
+}
+
+#[cfg(target_family = "wasm")]
+pub(crate) use web::*;
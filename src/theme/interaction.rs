@@ -4,15 +4,15 @@ use crate::{AppSet, asset_tracking::LoadResource, audio::SoundEffect};
 
 pub(super) fn plugin(app: &mut App) {
     app.register_type::<InteractionPalette>();
-    app.load_resource::<InteractionAssets>();
+    // Buttons work without their sounds, so don't make the loading screen wait for them.
+    app.load_resource_in_background::<InteractionAssets>();
     app.add_systems(
         Update,
         (
             trigger_on_press,
             apply_interaction_palette,
-            trigger_interaction_sound_effect,
+            trigger_interaction_sound_effect.run_if(resource_exists::<InteractionAssets>),
         )
-            .run_if(resource_exists::<InteractionAssets>)
             .in_set(AppSet::ChangeUi),
     );
 }
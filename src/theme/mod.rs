@@ -12,12 +12,12 @@ pub(crate) mod prelude {
     pub(crate) use super::{
         interaction::InteractionPalette,
         palette as ui_palette,
-        widgets::{Containers as _, Widgets as _},
+        widgets::{Containers as _, OnSelect, OnSliderChange, OnToggle, Widgets as _},
     };
 }
 
 use bevy::prelude::*;
 
 pub(super) fn plugin(app: &mut App) {
-    app.add_plugins(interaction::plugin);
+    app.add_plugins((interaction::plugin, widgets::plugin));
 }
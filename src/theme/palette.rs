@@ -7,4 +7,9 @@ pub(crate) const BUTTON_TEXT: Color = Color::srgb(0.925, 0.925, 0.925);
 pub(crate) const LABEL_TEXT: Color = Color::srgb(0.867, 0.827, 0.412);
 pub(crate) const HEADER_TEXT: Color = Color::srgb(0.867, 0.827, 0.412);
 
+pub(crate) const SLIDER_FILL: Color = Color::srgb(0.867, 0.827, 0.412);
+
 pub(crate) const NODE_BACKGROUND: Color = Color::srgb(0.286, 0.478, 0.773);
+
+/// Darkens the game behind in-game menus.
+pub(crate) const OVERLAY_BACKGROUND: Color = Color::srgba(0.0, 0.0, 0.0, 0.7);
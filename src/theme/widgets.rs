@@ -1,8 +1,31 @@
 //! Helper traits for creating common widgets.
 
-use bevy::{ecs::system::EntityCommands, hierarchy::ChildBuild, prelude::*, ui::Val::*};
+use std::ops::RangeInclusive;
 
-use crate::theme::{interaction::InteractionPalette, palette::*};
+use bevy::{
+    ecs::system::EntityCommands, hierarchy::ChildBuild, prelude::*, ui::RelativeCursorPosition,
+    ui::Val::*,
+};
+
+use crate::{
+    AppSet,
+    theme::{
+        interaction::{InteractionPalette, OnPress},
+        palette::*,
+    },
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<Slider>();
+    app.register_type::<Checkbox>();
+    app.register_type::<Dropdown>();
+    app.add_systems(
+        Update,
+        (drag_slider, update_slider_ui)
+            .chain()
+            .in_set(AppSet::ChangeUi),
+    );
+}
 
 /// An extension trait for spawning UI widgets.
 pub(crate) trait Widgets {
@@ -14,6 +37,31 @@ pub(crate) trait Widgets {
 
     /// Spawn a simple text label.
     fn label(&mut self, text: impl Into<String>) -> EntityCommands;
+
+    /// Spawn a labeled slider that can be dragged between the bounds of `range`.
+    /// The value is rounded to multiples of `step`.
+    /// Observe [`OnSliderChange`] on the returned entity to react to changes.
+    fn slider(
+        &mut self,
+        text: impl Into<String>,
+        value: f32,
+        range: RangeInclusive<f32>,
+        step: f32,
+    ) -> EntityCommands;
+
+    /// Spawn a labeled checkbox.
+    /// Observe [`OnToggle`] on the returned entity to react to changes.
+    fn checkbox(&mut self, text: impl Into<String>, checked: bool) -> EntityCommands;
+
+    /// Spawn a labeled dropdown that expands into a list of `options` when pressed.
+    /// `selected` is clamped to the available options.
+    /// Observe [`OnSelect`] on the returned entity to react to changes.
+    fn dropdown(
+        &mut self,
+        text: impl Into<String>,
+        options: Vec<String>,
+        selected: usize,
+    ) -> EntityCommands;
 }
 
 impl<T: Spawn> Widgets for T {
@@ -89,6 +137,419 @@ impl<T: Spawn> Widgets for T {
         ));
         entity
     }
+
+    fn slider(
+        &mut self,
+        text: impl Into<String>,
+        value: f32,
+        range: RangeInclusive<f32>,
+        step: f32,
+    ) -> EntityCommands {
+        let mut entity = self.spawn((Name::new("Slider"), setting_row_node()));
+        let mut fill = Entity::PLACEHOLDER;
+        let mut value_text = Entity::PLACEHOLDER;
+        entity.with_children(|children| {
+            ChildBuild::spawn(children, setting_label(text));
+            ChildBuild::spawn(
+                children,
+                (
+                    Name::new("Slider Track"),
+                    SliderTrack,
+                    Button,
+                    RelativeCursorPosition::default(),
+                    Node {
+                        width: Px(200.0),
+                        height: Px(40.0),
+                        ..default()
+                    },
+                    BackgroundColor(NODE_BACKGROUND),
+                    InteractionPalette {
+                        none: NODE_BACKGROUND,
+                        hovered: BUTTON_HOVERED_BACKGROUND,
+                        pressed: BUTTON_PRESSED_BACKGROUND,
+                    },
+                ),
+            )
+            .with_children(|track| {
+                fill = ChildBuild::spawn(
+                    track,
+                    (
+                        Name::new("Slider Fill"),
+                        Node {
+                            height: Percent(100.0),
+                            ..default()
+                        },
+                        BackgroundColor(SLIDER_FILL),
+                    ),
+                )
+                .id();
+            });
+            value_text = ChildBuild::spawn(
+                children,
+                (
+                    Name::new("Slider Value"),
+                    Text::default(),
+                    TextFont::from_font_size(24.0),
+                    TextColor(LABEL_TEXT),
+                    Node {
+                        width: Px(60.0),
+                        ..default()
+                    },
+                ),
+            )
+            .id();
+        });
+        entity.insert(Slider {
+            value,
+            min: *range.start(),
+            max: *range.end(),
+            step,
+            fill,
+            value_text,
+        });
+        entity
+    }
+
+    fn checkbox(&mut self, text: impl Into<String>, checked: bool) -> EntityCommands {
+        let mut entity = self.spawn((Name::new("Checkbox"), setting_row_node()));
+        let mut check_mark = Entity::PLACEHOLDER;
+        entity.with_children(|children| {
+            ChildBuild::spawn(children, setting_label(text));
+            ChildBuild::spawn(
+                children,
+                (
+                    Name::new("Checkbox Box"),
+                    Button,
+                    Node {
+                        width: Px(40.0),
+                        height: Px(40.0),
+                        justify_content: JustifyContent::Center,
+                        align_items: AlignItems::Center,
+                        ..default()
+                    },
+                    BackgroundColor(NODE_BACKGROUND),
+                    InteractionPalette {
+                        none: NODE_BACKGROUND,
+                        hovered: BUTTON_HOVERED_BACKGROUND,
+                        pressed: BUTTON_PRESSED_BACKGROUND,
+                    },
+                ),
+            )
+            .observe(toggle_checkbox)
+            .with_children(|checkbox| {
+                check_mark = ChildBuild::spawn(
+                    checkbox,
+                    (
+                        Name::new("Checkbox Check Mark"),
+                        Text(check_mark_text(checked).to_string()),
+                        TextFont::from_font_size(32.0),
+                        TextColor(BUTTON_TEXT),
+                    ),
+                )
+                .id();
+            });
+        });
+        entity.insert(Checkbox {
+            checked,
+            check_mark,
+        });
+        entity
+    }
+
+    fn dropdown(
+        &mut self,
+        text: impl Into<String>,
+        options: Vec<String>,
+        selected: usize,
+    ) -> EntityCommands {
+        let selected = selected.min(options.len().saturating_sub(1));
+        let mut entity = self.spawn((Name::new("Dropdown"), setting_row_node()));
+        let dropdown = entity.id();
+        let mut selected_text = Entity::PLACEHOLDER;
+        let mut option_list = Entity::PLACEHOLDER;
+        entity.with_children(|children| {
+            ChildBuild::spawn(children, setting_label(text));
+            ChildBuild::spawn(
+                children,
+                (
+                    Name::new("Dropdown Column"),
+                    Node {
+                        flex_direction: FlexDirection::Column,
+                        ..default()
+                    },
+                ),
+            )
+            .with_children(|column| {
+                ChildBuild::spawn(column, dropdown_button_bundle("Dropdown Selected"))
+                    .observe(toggle_dropdown)
+                    .with_children(|button| {
+                        selected_text = ChildBuild::spawn(
+                            button,
+                            dropdown_text_bundle(
+                                options.get(selected).cloned().unwrap_or_default(),
+                            ),
+                        )
+                        .id();
+                    });
+                option_list = ChildBuild::spawn(
+                    column,
+                    (
+                        Name::new("Dropdown Options"),
+                        Node {
+                            display: Display::None,
+                            flex_direction: FlexDirection::Column,
+                            ..default()
+                        },
+                    ),
+                )
+                .with_children(|list| {
+                    for (index, option) in options.iter().enumerate() {
+                        ChildBuild::spawn(
+                            list,
+                            (
+                                dropdown_button_bundle("Dropdown Option"),
+                                DropdownOption { dropdown, index },
+                            ),
+                        )
+                        .observe(select_dropdown_option)
+                        .with_children(|button| {
+                            ChildBuild::spawn(button, dropdown_text_bundle(option.clone()));
+                        });
+                    }
+                })
+                .id();
+            });
+        });
+        entity.insert(Dropdown {
+            options,
+            selected,
+            selected_text,
+            option_list,
+        });
+        entity
+    }
+}
+
+fn setting_row_node() -> Node {
+    Node {
+        width: Px(500.0),
+        justify_content: JustifyContent::SpaceBetween,
+        align_items: AlignItems::Center,
+        column_gap: Px(10.0),
+        ..default()
+    }
+}
+
+fn setting_label(text: impl Into<String>) -> impl Bundle {
+    (
+        Name::new("Setting Label"),
+        Text(text.into()),
+        TextFont::from_font_size(24.0),
+        TextColor(LABEL_TEXT),
+    )
+}
+
+fn dropdown_button_bundle(name: &'static str) -> impl Bundle {
+    (
+        Name::new(name),
+        Button,
+        Node {
+            width: Px(200.0),
+            height: Px(40.0),
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            ..default()
+        },
+        BackgroundColor(NODE_BACKGROUND),
+        InteractionPalette {
+            none: NODE_BACKGROUND,
+            hovered: BUTTON_HOVERED_BACKGROUND,
+            pressed: BUTTON_PRESSED_BACKGROUND,
+        },
+    )
+}
+
+fn dropdown_text_bundle(text: String) -> impl Bundle {
+    (
+        Name::new("Dropdown Text"),
+        Text(text),
+        TextFont::from_font_size(24.0),
+        TextColor(BUTTON_TEXT),
+    )
+}
+
+/// Event triggered on a [`Widgets::slider`] when the user drags it to a new value.
+#[derive(Event, Debug)]
+pub(crate) struct OnSliderChange(pub(crate) f32);
+
+/// Event triggered on a [`Widgets::checkbox`] when the user toggles it.
+#[derive(Event, Debug)]
+pub(crate) struct OnToggle(pub(crate) bool);
+
+/// Event triggered on a [`Widgets::dropdown`] with the index of the option the user selected.
+#[derive(Event, Debug)]
+pub(crate) struct OnSelect(pub(crate) usize);
+
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component)]
+pub(crate) struct Slider {
+    pub(crate) value: f32,
+    min: f32,
+    max: f32,
+    step: f32,
+    fill: Entity,
+    value_text: Entity,
+}
+
+impl Slider {
+    fn fraction(&self) -> f32 {
+        (self.value - self.min) / (self.max - self.min)
+    }
+
+    fn format_value(&self) -> String {
+        // Show as many decimals as the step needs.
+        let decimals = (-self.step.log10()).ceil().max(0.0) as usize;
+        format!("{:.*}", decimals, self.value)
+    }
+}
+
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component)]
+struct SliderTrack;
+
+fn drag_slider(
+    q_track: Query<(&Interaction, &RelativeCursorPosition, &Parent), With<SliderTrack>>,
+    mut q_slider: Query<&mut Slider>,
+    mut commands: Commands,
+) {
+    for (interaction, cursor, parent) in &q_track {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        let Some(cursor) = cursor.normalized else {
+            continue;
+        };
+        let Ok(mut slider) = q_slider.get_mut(parent.get()) else {
+            continue;
+        };
+        let raw_value = slider.min + cursor.x.clamp(0.0, 1.0) * (slider.max - slider.min);
+        let value = ((raw_value / slider.step).round() * slider.step).clamp(slider.min, slider.max);
+        if value != slider.value {
+            slider.value = value;
+            commands.trigger_targets(OnSliderChange(value), parent.get());
+        }
+    }
+}
+
+fn update_slider_ui(
+    q_slider: Query<&Slider, Changed<Slider>>,
+    mut q_node: Query<&mut Node>,
+    mut q_text: Query<&mut Text>,
+) {
+    for slider in &q_slider {
+        if let Ok(mut fill) = q_node.get_mut(slider.fill) {
+            fill.width = Percent(slider.fraction() * 100.0);
+        }
+        if let Ok(mut text) = q_text.get_mut(slider.value_text) {
+            text.0 = slider.format_value();
+        }
+    }
+}
+
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component)]
+pub(crate) struct Checkbox {
+    pub(crate) checked: bool,
+    check_mark: Entity,
+}
+
+fn check_mark_text(checked: bool) -> &'static str {
+    if checked { "X" } else { "" }
+}
+
+fn toggle_checkbox(
+    trigger: Trigger<OnPress>,
+    q_parent: Query<&Parent>,
+    mut q_checkbox: Query<&mut Checkbox>,
+    mut q_text: Query<&mut Text>,
+    mut commands: Commands,
+) {
+    let Ok(parent) = q_parent.get(trigger.entity()) else {
+        return;
+    };
+    let Ok(mut checkbox) = q_checkbox.get_mut(parent.get()) else {
+        return;
+    };
+    checkbox.checked = !checkbox.checked;
+    if let Ok(mut text) = q_text.get_mut(checkbox.check_mark) {
+        text.0 = check_mark_text(checkbox.checked).to_string();
+    }
+    commands.trigger_targets(OnToggle(checkbox.checked), parent.get());
+}
+
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component)]
+pub(crate) struct Dropdown {
+    pub(crate) options: Vec<String>,
+    pub(crate) selected: usize,
+    selected_text: Entity,
+    option_list: Entity,
+}
+
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component)]
+struct DropdownOption {
+    dropdown: Entity,
+    index: usize,
+}
+
+fn toggle_dropdown(
+    trigger: Trigger<OnPress>,
+    q_parent: Query<&Parent>,
+    q_dropdown: Query<&Dropdown>,
+    mut q_node: Query<&mut Node>,
+) {
+    // The selected option button is nested in a column inside the dropdown.
+    let Some(dropdown) = q_parent
+        .iter_ancestors(trigger.entity())
+        .find_map(|ancestor| q_dropdown.get(ancestor).ok())
+    else {
+        return;
+    };
+    let Ok(mut option_list) = q_node.get_mut(dropdown.option_list) else {
+        return;
+    };
+    option_list.display = match option_list.display {
+        Display::None => Display::Flex,
+        _ => Display::None,
+    };
+}
+
+fn select_dropdown_option(
+    trigger: Trigger<OnPress>,
+    q_option: Query<&DropdownOption>,
+    mut q_dropdown: Query<&mut Dropdown>,
+    mut q_node: Query<&mut Node>,
+    mut q_text: Query<&mut Text>,
+    mut commands: Commands,
+) {
+    let Ok(option) = q_option.get(trigger.entity()) else {
+        return;
+    };
+    let Ok(mut dropdown) = q_dropdown.get_mut(option.dropdown) else {
+        return;
+    };
+    dropdown.selected = option.index;
+    if let (Ok(mut text), Some(selected)) = (
+        q_text.get_mut(dropdown.selected_text),
+        dropdown.options.get(option.index),
+    ) {
+        text.0 = selected.clone();
+    }
+    if let Ok(mut option_list) = q_node.get_mut(dropdown.option_list) {
+        option_list.display = Display::None;
+    }
+    commands.trigger_targets(OnSelect(option.index), option.dropdown);
 }
 
 /// An extension trait for spawning UI containers.
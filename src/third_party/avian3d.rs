@@ -1,8 +1,10 @@
 use avian3d::prelude::*;
 use bevy::prelude::*;
+use serde::Deserialize;
 
 pub(super) fn plugin(app: &mut App) {
     app.add_plugins(PhysicsPlugins::default());
+    app.register_type::<PhysicsMaterial>();
 }
 
 #[derive(Debug, PhysicsLayer, Default)]
@@ -12,3 +14,13 @@ pub(crate) enum CollisionLayer {
     Player,
     Prop,
 }
+
+/// What a body is made of. Decides which sounds it makes when something hits it.
+#[derive(Debug, Component, Reflect, Clone, Copy, PartialEq, Eq, Hash, Default, Deserialize)]
+#[reflect(Component, Default, Debug, PartialEq, Hash)]
+pub(crate) enum PhysicsMaterial {
+    #[default]
+    Wood,
+    Metal,
+    Stone,
+}
@@ -3,14 +3,26 @@ use bevy_trenchbroom::{bsp::base_classes::BspWorldspawn, class::QuakeClass, prel
 use proxy::RegisterProxies as _;
 
 use crate::{
-    gameplay::{npc::Npc, player::Player},
-    props::RegisterProps as _,
+    gameplay::{
+        door::Door,
+        level::LevelExit,
+        npc::{Npc, patrol::PathCorner},
+        player::Player,
+        room::Room,
+        save::RegisterSavedClass as _,
+    },
+    props::{PropDefinitions, RegisterProps as _},
 };
 
+pub(crate) mod preload;
 mod proxy;
+pub(crate) mod target;
 
 pub(super) fn plugin(app: &mut App) {
-    app.add_plugins(TrenchBroomPlugin({
+    // The prop classes are generated from their definitions, so those need to be read first.
+    app.init_resource::<PropDefinitions>();
+    let config = {
+        let props = app.world().resource::<PropDefinitions>();
         let config = TrenchBroomConfig::new("foxtrot")
             .generic_material_extension("material.toml")
             .texture_exclusions(
@@ -21,17 +33,24 @@ pub(super) fn plugin(app: &mut App) {
             )
             // In Wasm, TrenchBroom classes are not automatically registered.
             // So, we need to manually register the classes here
-            .register_props()
+            .register_props(props)
             .register_proxies()
             .register_class::<Worldspawn>()
-            .register_class::<Npc>()
+            .register_class::<LevelExit>()
+            .register_class::<Door>()
+            .register_class::<Room>()
+            .register_saved_class::<Npc>()
+            .register_class::<PathCorner>()
+            .register_class::<target::Target>()
+            .register_class::<target::TargetName>()
             .register_class::<Player>();
         #[cfg(target_arch = "wasm32")]
         let config = config.no_bsp_lighting(true);
         config
-    }));
+    };
+    app.add_plugins(TrenchBroomPlugin(config));
     app.add_systems(Startup, write_trenchbroom_config);
-    app.add_plugins(proxy::plugin);
+    app.add_plugins((preload::plugin, proxy::plugin, target::plugin));
 }
 
 fn write_trenchbroom_config(server: Res<TrenchBroomServer>) {
@@ -59,6 +78,12 @@ pub(crate) fn fix_gltf_rotation(mut world: EntityWorldMut) {
     trenchbroom_gltf_rotation_fix(&mut world);
 }
 
+/// Use with `#[require(Visibility(hidden))]` for brush entities that only exist for their collider,
+/// e.g. trigger volumes.
+pub(crate) fn hidden() -> Visibility {
+    Visibility::Hidden
+}
+
 pub(crate) trait GetTrenchbroomModelPath: QuakeClass {
     fn scene_path() -> String {
         format!(
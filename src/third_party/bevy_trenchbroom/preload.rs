@@ -0,0 +1,64 @@
+//! Knows which assets each TrenchBroom class needs, so that levels can preload exactly
+//! the assets of the entities placed in their map.
+
+use bevy::{
+    prelude::*,
+    utils::{HashMap, HashSet},
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<ClassPreloads>();
+}
+
+/// An entity placed in a map, as recorded by the build script.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct MapEntity {
+    pub(crate) classname: &'static str,
+    pub(crate) properties: &'static [(&'static str, &'static str)],
+}
+
+impl MapEntity {
+    /// The value of a property, if it was set in the map.
+    pub(crate) fn property(&self, key: &str) -> Option<&'static str> {
+        self.properties
+            .iter()
+            .find(|(property, _)| *property == key)
+            .map(|(_, value)| *value)
+    }
+}
+
+/// A function that starts loading all assets needed by an entity of a class.
+type PreloadFn = Box<dyn Fn(&MapEntity, &AssetServer) -> Vec<UntypedHandle> + Send + Sync>;
+
+/// Maps TrenchBroom classnames to the assets they need.
+/// Classes that are not registered here are assumed to need no assets of their own.
+#[derive(Resource, Default)]
+pub(crate) struct ClassPreloads(HashMap<String, PreloadFn>);
+
+impl ClassPreloads {
+    /// Registers the assets that need to be loaded before a level containing the class is spawned.
+    /// The preload is given each entity of the class, so it can take its properties into account.
+    pub(crate) fn insert(
+        &mut self,
+        classname: impl Into<String>,
+        preload: impl Fn(&MapEntity, &AssetServer) -> Vec<UntypedHandle> + Send + Sync + 'static,
+    ) {
+        self.0.insert(classname.into(), Box::new(preload));
+    }
+
+    /// Starts loading the assets of all given entities.
+    /// Assets needed by several entities are only returned once.
+    pub(crate) fn load<'a>(
+        &self,
+        entities: impl IntoIterator<Item = &'a MapEntity>,
+        assets: &AssetServer,
+    ) -> Vec<UntypedHandle> {
+        let mut seen = HashSet::new();
+        entities
+            .into_iter()
+            .filter_map(|entity| Some((entity, self.0.get(entity.classname)?)))
+            .flat_map(|(entity, preload)| preload(entity, assets))
+            .filter(|handle| seen.insert(handle.id()))
+            .collect()
+    }
+}
@@ -0,0 +1,40 @@
+//! Quake-style entity links: an entity's `target` refers to the `targetname` of other entities.
+//! TrenchBroom draws these links in the editor.
+
+use bevy::prelude::*;
+use bevy_trenchbroom::prelude::*;
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<(Target, TargetName)>();
+}
+
+/// Links to all entities whose [`TargetName`] matches.
+#[derive(BaseClass, Component, Debug, Clone, Default, Reflect, PartialEq, Eq)]
+#[reflect(Component, Default, Debug)]
+pub(crate) struct Target {
+    pub(crate) target: String,
+}
+
+/// A name that [`Target`]s can refer to.
+#[derive(BaseClass, Component, Debug, Clone, Default, Reflect, PartialEq, Eq)]
+#[reflect(Component, Default, Debug)]
+pub(crate) struct TargetName {
+    pub(crate) targetname: String,
+}
+
+impl Target {
+    /// Finds the first entity with a matching [`TargetName`].
+    /// An empty target never matches anything.
+    pub(crate) fn find<'a>(
+        &self,
+        candidates: impl IntoIterator<Item = (Entity, &'a TargetName)>,
+    ) -> Option<Entity> {
+        if self.target.is_empty() {
+            return None;
+        }
+        candidates
+            .into_iter()
+            .find(|(_, name)| name.targetname == self.target)
+            .map(|(entity, _)| entity)
+    }
+}
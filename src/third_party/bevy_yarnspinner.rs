@@ -1,19 +1,22 @@
 use bevy::prelude::*;
 use bevy_trenchbroom::prelude::BaseClass;
 use bevy_yarnspinner::{events::DialogueCompleteEvent, prelude::*};
-use bevy_yarnspinner_example_dialogue_view::prelude::*;
 
 use crate::screens::Screen;
 
 pub(super) fn plugin(app: &mut App) {
-    app.register_type::<YarnNode>();
+    app.register_type::<(YarnNode, InDialogue)>();
 
-    app.add_plugins((
-        // In Wasm, we need to load the dialogue file manually. If we're not targeting Wasm, we can just use `YarnSpinnerPlugin::default()` instead.
-        YarnSpinnerPlugin::with_yarn_sources(vec![YarnFileSource::file("dialogue/npc.yarn")]),
-        ExampleYarnSpinnerDialogueViewPlugin::default(),
-    ));
+    // In Wasm, we need to load the dialogue file manually. If we're not targeting Wasm, we can just use `YarnSpinnerPlugin::default()` instead.
+    // The dialogue is presented by `gameplay::dialogue_view`.
+    app.add_plugins(YarnSpinnerPlugin::with_yarn_sources(vec![
+        YarnFileSource::file("dialogue/npc.yarn"),
+    ]));
     app.add_systems(OnEnter(Screen::Gameplay), setup_dialogue_runner);
+    app.add_systems(
+        Update,
+        leave_dialogue.run_if(on_event::<DialogueCompleteEvent>),
+    );
     app.add_systems(
         OnExit(Screen::Gameplay),
         abort_all_dialogues_when_leaving_gameplay,
@@ -39,6 +42,17 @@ fn abort_all_dialogues_when_leaving_gameplay(
     }
 }
 
+/// Marks the entity whose [`YarnNode`] the player is currently talking to.
+#[derive(Component, Debug, Default, Reflect)]
+#[reflect(Component, Default, Debug)]
+pub(crate) struct InDialogue;
+
+fn leave_dialogue(mut commands: Commands, q_in_dialogue: Query<Entity, With<InDialogue>>) {
+    for entity in &q_in_dialogue {
+        commands.entity(entity).remove::<InDialogue>();
+    }
+}
+
 pub(crate) fn is_dialogue_running(dialogue_runner: Option<Single<&DialogueRunner>>) -> bool {
     dialogue_runner.is_some_and(|dialogue_runner| dialogue_runner.is_running())
 }